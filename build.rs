@@ -0,0 +1,26 @@
+use std::{env, process::Command};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-check-cfg=cfg(no_const_generics)");
+
+    // `min_const_generics` was stabilized in Rust 1.51, older compilers
+    // fall back to the macro based array storages
+    match rustc_minor_version() {
+        Some(minor) if minor < 51 => println!("cargo:rustc-cfg=no_const_generics"),
+        _ => (),
+    }
+}
+
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC")?;
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    let version = std::str::from_utf8(&output.stdout).ok()?;
+
+    let mut pieces = version.split('.');
+    if pieces.next() != Some("rustc 1") {
+        return None
+    }
+
+    pieces.next()?.parse().ok()
+}
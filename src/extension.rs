@@ -4,6 +4,91 @@ pub trait Extension<T> {
     unsafe fn extend_from_slice(&mut self, slice: &[T]);
 
     unsafe fn grow(&mut self, additional: usize, value: T);
+
+    unsafe fn repeat(&mut self, n: usize);
+}
+
+pub trait SpecExtend<T, I> {
+    fn spec_extend(&mut self, iter: I);
+}
+
+/// Extend `vec` by writing directly into it's spare capacity
+///
+/// Each batch of items is written into the spare capacity, and then
+/// the length is updated once for the whole batch
+fn extend_spare<T, S: ?Sized + Storage<T>, I: Iterator<Item = T>>(vec: &mut GenericVec<T, S>, mut iter: I) {
+    while let Some(value) = try_extend_spare(vec, &mut iter) {
+        // the storage couldn't grow, so this will report the error
+        vec.push(value);
+    }
+}
+
+/// Like [`extend_spare`], but stops if the storage can't grow
+///
+/// Returns the first item that didn't fit, the rest of the items are left in `iter`
+pub(crate) fn try_extend_spare<T, S: ?Sized + Storage<T>, I: Iterator<Item = T>>(
+    vec: &mut GenericVec<T, S>,
+    iter: &mut I,
+) -> Option<T> {
+    loop {
+        let (lower, upper) = iter.size_hint();
+
+        // if all of the items already fit, there's no need to reserve,
+        // otherwise only reserve what we know will be used
+        match upper {
+            Some(upper) if upper <= vec.remaining_capacity() => (),
+            _ => {
+                let _ = vec.try_reserve(lower);
+            }
+        }
+
+        let mut spare = vec.spare_capacity_mut();
+
+        while !spare.is_full() {
+            match iter.next() {
+                // Safety
+                //
+                // `spare` isn't full
+                Some(value) => unsafe {
+                    spare.push_unchecked(value);
+                },
+                None => break,
+            }
+        }
+
+        let is_full = spare.is_full();
+
+        // Safety
+        //
+        // `spare` was obtained from `spare_capacity_mut`
+        unsafe { crate::save_spare!(spare, vec) }
+
+        if !is_full {
+            return None
+        }
+
+        // the spare capacity ran out, so there may be more items than expected
+        let value = iter.next()?;
+
+        if !vec.try_reserve(1) {
+            return Some(value)
+        }
+
+        // Safety
+        //
+        // we reserved space for 1 more element
+        unsafe {
+            vec.push_unchecked(value);
+        }
+    }
+}
+
+impl<T, S: ?Sized + Storage<T>, I: Iterator<Item = T>> SpecExtend<T, I> for GenericVec<T, S> {
+    #[cfg(feature = "nightly")]
+    default fn spec_extend(&mut self, iter: I) { extend_spare(self, iter) }
+
+    #[cfg(not(feature = "nightly"))]
+    fn spec_extend(&mut self, iter: I) { extend_spare(self, iter) }
 }
 
 fn clone_extend_from_slice<T, S: ?Sized + Storage<T>>(vec: &mut GenericVec<T, S>, slice: &[T])
@@ -58,6 +143,59 @@ where
     }
 }
 
+fn clone_repeat<T, S: ?Sized + Storage<T>>(vec: &mut GenericVec<T, S>, n: usize)
+where
+    T: Clone,
+{
+    let (init, mut spare) = vec.split_at_spare_mut();
+
+    for _ in 1..n {
+        for value in init.iter() {
+            // Safety
+            //
+            // `clone_repeat` is only called from `Extension::repeat` which has the
+            // pre-condition that there must be enough remaining capacity for `n - 1`
+            // more copies of the elements
+            unsafe {
+                spare.push_unchecked(value.clone());
+            }
+        }
+    }
+
+    // Safety
+    //
+    // `spare` was obtained from `split_at_spare_mut`
+    unsafe { crate::save_spare!(spare, vec) }
+}
+
+/// Repeat the elements of `vec` `n` times, by copying them in a few large chunks
+///
+/// # Safety
+///
+/// there must be enough remaining capacity for `n - 1` more copies of the elements
+pub(crate) unsafe fn copy_repeat<T: Copy, S: ?Sized + Storage<T>>(vec: &mut GenericVec<T, S>, n: usize) {
+    let len = vec.len();
+    let new_len = len * n;
+    let mut filled = len;
+
+    // Safety
+    //
+    // * the caller ensures that there is enough capacity for `n` copies of the elements
+    // * `T: Copy`, so there is nothing to drop
+    unsafe {
+        let ptr = vec.as_mut_ptr();
+
+        // double the copied elements each time
+        while filled < new_len {
+            let count = filled.min(new_len - filled);
+            ptr.add(filled).copy_from_nonoverlapping(ptr, count);
+            filled += count;
+        }
+
+        vec.set_len_unchecked(new_len);
+    }
+}
+
 impl<T, S: ?Sized + Storage<T>> Extension<T> for GenericVec<T, S>
 where
     T: Clone,
@@ -73,6 +211,12 @@ where
 
     #[cfg(not(feature = "nightly"))]
     unsafe fn grow(&mut self, additional: usize, value: T) { clone_grow(self, additional, value) }
+
+    #[cfg(feature = "nightly")]
+    default unsafe fn repeat(&mut self, n: usize) { clone_repeat(self, n) }
+
+    #[cfg(not(feature = "nightly"))]
+    unsafe fn repeat(&mut self, n: usize) { clone_repeat(self, n) }
 }
 
 #[cfg(feature = "nightly")]
@@ -89,22 +233,13 @@ where
         unsafe { self.extend_from_slice_unchecked(slice) }
     }
 
-    default unsafe fn grow(&mut self, additional: usize, value: T) {
-        // Safety
-        //
-        // * `Extension::grow`'s precondition ensure that
-        //   there is enough capacity for `additional` elements
-        let len = self.len();
-        unsafe {
-            self.set_len_unchecked(len.wrapping_add(additional));
-        }
-        let mut ptr = self.storage.as_mut_ptr();
+    // `grow_copy` won't need to reserve, because `Extension::grow`'s
+    // precondition ensures that there is enough capacity
+    default unsafe fn grow(&mut self, additional: usize, value: T) { self.grow_copy(additional, value) }
 
-        for _ in 0..additional {
-            unsafe {
-                ptr.write(value);
-                ptr = ptr.add(1);
-            }
-        }
-    }
+    // Safety
+    //
+    // `Extension::repeat`'s precondition ensures that there
+    // is enough capacity for `n` copies of the elements
+    unsafe fn repeat(&mut self, n: usize) { unsafe { copy_repeat(self, n) } }
 }
@@ -4,14 +4,18 @@ use crate::{raw::StorageWithCapacity, GenericVec, Storage};
 use core::{
     borrow::{Borrow, BorrowMut},
     hash::{Hash, Hasher},
+    mem::MaybeUninit,
     ops::{Index, IndexMut},
     ptr::NonNull,
     slice::SliceIndex,
 };
 
-#[cfg(feature = "alloc")]
-use std::vec::Vec;
+#[cfg(any(doc, feature = "alloc"))]
+use std::{boxed::Box, vec::Vec};
 
+/// On stable, this clones each element, because picking a `memcpy` for `Copy` elements
+/// needs specialization. Use [`GenericVec::clone_copy`] to copy a vector of `Copy`
+/// elements with a single `memcpy` on stable.
 impl<T, S: StorageWithCapacity<T>> Clone for GenericVec<T, S>
 where
     T: Clone,
@@ -25,6 +29,15 @@ where
     fn clone_from(&mut self, source: &Self) { self.clone_from(source); }
 }
 
+/// Copies the elements of the slice with a single `memcpy`
+impl<T: Copy, S: StorageWithCapacity<T>> From<&[T]> for GenericVec<T, S> {
+    fn from(slice: &[T]) -> Self {
+        let mut vec = Self::with_capacity(slice.len());
+        vec.extend_from_copy_slice(slice);
+        vec
+    }
+}
+
 impl<T, S: StorageWithCapacity<T>> Default for GenericVec<T, S> {
     fn default() -> Self { Self::with_storage(Default::default()) }
 }
@@ -83,12 +96,24 @@ impl<T, S: ?Sized + Storage<T>> BorrowMut<[T]> for GenericVec<T, S> {
     fn borrow_mut(&mut self) -> &mut [T] { self }
 }
 
-#[cfg(any(doc, feature = "nightly"))]
-impl<T, const N: usize> From<[T; N]> for crate::ArrayVec<T, N> {
-    fn from(array: [T; N]) -> Self { Self::from_array(array) }
+#[cfg(feature = "zeroize")]
+#[cfg_attr(doc, doc(cfg(feature = "zeroize")))]
+impl<T: zeroize::DefaultIsZeroes, S: ?Sized + Storage<T>> zeroize::Zeroize for GenericVec<T, S> {
+    fn zeroize(&mut self) { GenericVec::zeroize(self) }
 }
 
-#[cfg(any(doc, feature = "nightly"))]
+/// Arrays that are too large for the `ArrayVec` don't compile
+///
+/// ```compile_fail
+/// # use generic_vec::ArrayVec;
+/// let vec = ArrayVec::<i32, 2>::from([1, 2, 3]);
+/// ```
+#[cfg(any(doc, not(no_const_generics)))]
+impl<T, const M: usize, const N: usize> From<[T; M]> for crate::ArrayVec<T, N> {
+    fn from(array: [T; M]) -> Self { Self::from_array_in(array, crate::raw::UninitBuffer::uninit()) }
+}
+
+#[cfg(any(doc, not(no_const_generics)))]
 impl<T: Copy, const N: usize> From<[T; N]> for crate::InitArrayVec<T, N> {
     fn from(array: [T; N]) -> Self { crate::InitArrayVec::<T, N>::new(array) }
 }
@@ -146,6 +171,96 @@ impl<T, A: std::alloc::Allocator> From<crate::HeapVec<T, A>> for Vec<T, A> {
     }
 }
 
+#[cfg(any(doc, feature = "alloc"))]
+#[cfg_attr(doc, doc(cfg(feature = "alloc")))]
+impl<T> From<Box<[T]>> for GenericVec<T, crate::raw::BoxedSlice<T>> {
+    fn from(boxed: Box<[T]>) -> Self {
+        let len = boxed.len();
+        let boxed = Box::into_raw(boxed) as *mut [MaybeUninit<T>];
+
+        // Safety
+        //
+        // * `MaybeUninit<T>` has the same layout as `T`
+        // * all `len` elements of the boxed slice are initialized
+        unsafe { Self::from_raw_parts(len, crate::raw::BoxedSlice::from_boxed(Box::from_raw(boxed))) }
+    }
+}
+
+#[cfg(any(doc, feature = "alloc"))]
+#[cfg_attr(doc, doc(cfg(feature = "alloc")))]
+impl<T> From<GenericVec<T, crate::raw::BoxedSlice<T>>> for Box<[T]> {
+    /// This doesn't reallocate if the vector is full
+    fn from(vec: GenericVec<T, crate::raw::BoxedSlice<T>>) -> Self {
+        let (len, storage) = vec.into_raw_parts();
+        unsafe { crate::raw::boxed::boxed_into_vec(storage.into_boxed(), len).into_boxed_slice() }
+    }
+}
+
+#[cfg(any(doc, feature = "alloc"))]
+#[cfg_attr(doc, doc(cfg(feature = "alloc")))]
+impl<T> From<GenericVec<T, Box<[MaybeUninit<T>]>>> for Box<[T]> {
+    /// This doesn't reallocate if the vector is full
+    fn from(vec: GenericVec<T, Box<[MaybeUninit<T>]>>) -> Self {
+        let (len, storage) = vec.into_raw_parts();
+        unsafe { crate::raw::boxed::boxed_into_vec(storage, len).into_boxed_slice() }
+    }
+}
+
+#[cfg(any(doc, feature = "alloc"))]
+#[cfg_attr(doc, doc(cfg(feature = "alloc")))]
+impl<T> From<Vec<T>> for GenericVec<T, Vec<MaybeUninit<T>>> {
+    fn from(vec: Vec<T>) -> Self {
+        let mut vec = core::mem::ManuallyDrop::new(vec);
+
+        let len = vec.len();
+        let cap = vec.capacity();
+        let ptr = vec.as_mut_ptr();
+
+        // Safety
+        //
+        // * `MaybeUninit<T>` has the same layout as `T`
+        // * the first `len` elements of the `Vec` are initialized
+        unsafe { Self::from_raw_parts(len, Vec::from_raw_parts(ptr.cast(), 0, cap)) }
+    }
+}
+
+#[cfg(any(doc, feature = "alloc"))]
+#[cfg_attr(doc, doc(cfg(feature = "alloc")))]
+impl<T> From<GenericVec<T, Vec<MaybeUninit<T>>>> for Vec<T> {
+    fn from(vec: GenericVec<T, Vec<MaybeUninit<T>>>) -> Self {
+        let (len, storage) = vec.into_raw_parts();
+        unsafe { crate::raw::boxed::uninit_into_vec(storage, len) }
+    }
+}
+
+#[cfg(any(doc, all(feature = "alloc", not(no_const_generics))))]
+#[cfg_attr(doc, doc(cfg(feature = "alloc")))]
+impl<T, const ALIGN: usize> From<crate::HeapVec<T>> for crate::AlignedHeapVec<T, ALIGN> {
+    /// This doesn't reallocate if `ALIGN` is not larger than the alignment of `T`
+    fn from(vec: crate::HeapVec<T>) -> Self {
+        let (len, storage) = vec.into_raw_parts();
+
+        match crate::raw::AlignedHeap::try_from_heap(storage) {
+            Ok(storage) => unsafe { Self::from_raw_parts(len, storage) },
+            Err(storage) => unsafe { crate::HeapVec::from_raw_parts(len, storage) }.convert(),
+        }
+    }
+}
+
+#[cfg(any(doc, all(feature = "alloc", not(no_const_generics))))]
+#[cfg_attr(doc, doc(cfg(feature = "alloc")))]
+impl<T, const ALIGN: usize> From<crate::AlignedHeapVec<T, ALIGN>> for crate::HeapVec<T> {
+    /// This doesn't reallocate if `ALIGN` is not larger than the alignment of `T`
+    fn from(vec: crate::AlignedHeapVec<T, ALIGN>) -> Self {
+        let (len, storage) = vec.into_raw_parts();
+
+        match storage.try_into_heap() {
+            Ok(storage) => unsafe { Self::from_raw_parts(len, storage) },
+            Err(storage) => unsafe { crate::AlignedHeapVec::from_raw_parts(len, storage) }.convert(),
+        }
+    }
+}
+
 impl<T, S: Storage<T> + ?Sized, I> Index<I> for GenericVec<T, S>
 where
     I: SliceIndex<[T]>,
@@ -1,22 +1,30 @@
 //! The [`Iterator`] types that can be created from a [`GenericVec`]
 
+mod collect;
 mod cursor;
 mod drain;
 mod drain_filter;
 mod into_iter;
+#[cfg(feature = "rayon")]
+mod par;
 mod raw_cursor;
 mod splice;
 
+pub use collect::{IteratorExt, Overflow};
 pub use cursor::Cursor;
 pub use drain::Drain;
 pub use drain_filter::DrainFilter;
 pub use into_iter::IntoIter;
+#[cfg(feature = "rayon")]
+#[cfg_attr(doc, doc(cfg(feature = "rayon")))]
+pub use par::{ParDrain, ParIntoIter};
 pub use raw_cursor::RawCursor;
 pub use splice::Splice;
 
 use core::iter::FromIterator;
 
 use crate::{
+    extension::SpecExtend,
     raw::{Storage, StorageWithCapacity},
     GenericVec,
 };
@@ -27,26 +35,21 @@ where
 {
     #[inline]
     fn from_iter<I: IntoIterator<Item = V>>(iter: I) -> Self {
-        let mut array = Self::default();
+        let iter = iter.into_iter();
+        let mut array = Self::with_capacity(iter.size_hint().0);
         array.extend(iter);
         array
     }
 }
 
 impl<T, S: ?Sized + Storage<T>> Extend<T> for GenericVec<T, S> {
-    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
-        let iter = iter.into_iter();
-        let _ = self.try_reserve(iter.size_hint().0);
-        #[allow(clippy::drop_ref)]
-        iter.for_each(|item| drop(self.push(item)));
-    }
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) { self.spec_extend(iter.into_iter()) }
 }
 
+/// This clones each element. Use [`GenericVec::extend_from_copy_slice`],
+/// or `GenericVec::from(slice)`, to copy a slice of `Copy` elements with a single `memcpy`
 impl<'a, T: 'a + Clone, S: ?Sized + Storage<T>> Extend<&'a T> for GenericVec<T, S> {
-    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
-        let iter = iter.into_iter();
-        let _ = self.try_reserve(iter.size_hint().0);
-        #[allow(clippy::drop_ref)]
-        iter.cloned().for_each(|item| drop(self.push(item)));
-    }
+    #[inline]
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) { self.spec_extend(iter.into_iter().cloned()) }
 }
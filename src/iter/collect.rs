@@ -0,0 +1,97 @@
+use crate::{extension::try_extend_spare, GenericVec, Storage};
+
+use core::iter::{Chain, Once};
+
+/// Extension methods for collecting an [`Iterator`] into a [`GenericVec`]
+///
+/// [`FromIterator`](core::iter::FromIterator) can only create storages that implement
+/// [`StorageWithCapacity`](crate::raw::StorageWithCapacity). These methods take the storage,
+/// or the vector, to collect into instead. So they also work with borrowed buffers, and
+/// storages that need some state to be created.
+///
+/// ```rust
+/// use generic_vec::{iter::IteratorExt, raw::UninitSlice, uninit_array};
+///
+/// let mut buf = uninit_array!(8);
+/// let vec = (1..=4).map(|x| x * x).collect_in(UninitSlice::<i32>::from_mut(&mut buf));
+/// assert_eq!(vec, [1, 4, 9, 16]);
+/// ```
+pub trait IteratorExt: Iterator + Sized {
+    /// Collect all items into a new [`GenericVec`] backed by `storage`
+    ///
+    /// # Panic
+    ///
+    /// May panic or reallocate if the storage can't hold all of the items
+    fn collect_in<S: Storage<Self::Item>>(self, storage: S) -> GenericVec<Self::Item, S> {
+        let mut vec = GenericVec::with_storage(storage);
+        vec.extend(self);
+        vec
+    }
+
+    /// Collect all items into a new [`GenericVec`] backed by `storage`,
+    /// stops if the storage can't hold all of the items
+    ///
+    /// On overflow, the vector holds all the items that fit, and the rest
+    /// of the items are in [`Overflow`]
+    ///
+    /// ```rust
+    /// use generic_vec::{iter::IteratorExt, raw::UninitBuffer};
+    ///
+    /// let (vec, overflow) = (0..5).try_collect_in(UninitBuffer::<[i32; 3]>::uninit()).unwrap_err();
+    /// assert_eq!(vec, [0, 1, 2]);
+    /// assert_eq!(overflow.item, 3);
+    /// assert!(overflow.into_iter().eq(3..5));
+    /// ```
+    #[allow(clippy::type_complexity)]
+    fn try_collect_in<S: Storage<Self::Item>>(
+        mut self,
+        storage: S,
+    ) -> Result<GenericVec<Self::Item, S>, (GenericVec<Self::Item, S>, Overflow<Self>)> {
+        let mut vec = GenericVec::with_storage(storage);
+
+        match try_extend_spare(&mut vec, &mut self) {
+            None => Ok(vec),
+            Some(item) => Err((vec, Overflow { item, rest: self })),
+        }
+    }
+
+    /// Collect all items into the end of `vec`
+    ///
+    /// # Panic
+    ///
+    /// May panic or reallocate if the vector can't hold all of the items
+    ///
+    /// ```rust
+    /// use generic_vec::{iter::IteratorExt, ArrayVec};
+    ///
+    /// let mut vec = ArrayVec::<i32, 4>::new();
+    /// vec.push(0);
+    /// (1..4).collect_into(&mut vec);
+    /// assert_eq!(vec, [0, 1, 2, 3]);
+    /// ```
+    fn collect_into<S: ?Sized + Storage<Self::Item>>(
+        self,
+        vec: &mut GenericVec<Self::Item, S>,
+    ) -> &mut GenericVec<Self::Item, S> {
+        vec.extend(self);
+        vec
+    }
+}
+
+impl<I: Iterator> IteratorExt for I {}
+
+/// The items that didn't fit, returned by [`IteratorExt::try_collect_in`]
+#[derive(Debug)]
+pub struct Overflow<I: Iterator> {
+    /// The first item that didn't fit
+    pub item: I::Item,
+    /// The rest of the items
+    pub rest: I,
+}
+
+impl<I: Iterator> IntoIterator for Overflow<I> {
+    type Item = I::Item;
+    type IntoIter = Chain<Once<I::Item>, I>;
+
+    fn into_iter(self) -> Self::IntoIter { core::iter::once(self.item).chain(self.rest) }
+}
@@ -30,6 +30,10 @@ impl<T, S: ?Sized + Storage<T>> Drop for IntoIter<T, S> {
             let len = self.vec.len();
             let index = self.index;
 
+            // the remaining elements aren't at the front of the storage, so the
+            // storage can't keep them, they will all be dropped
+            self.vec.storage.__on_drop__(0);
+
             let drop_alloc = DropAlloc(&mut self.vec.storage);
             let data = drop_alloc.0.as_mut_ptr().add(index);
             core::ptr::slice_from_raw_parts_mut(data, len.wrapping_sub(index)).drop_in_place();
@@ -90,6 +94,88 @@ impl<T, S: ?Sized + Storage<T>> IntoIter<T, S> {
     }
 }
 
+impl<T, S: Storage<T>> IntoIter<T, S> {
+    /// Drop all the remaining elements in the iterator, and return the backing storage
+    ///
+    /// ```rust
+    /// # use generic_vec::HeapVec;
+    /// let vec: HeapVec<i32> = (0..10).collect();
+    /// let mut iter = vec.into_iter();
+    /// assert_eq!(iter.next(), Some(0));
+    ///
+    /// let storage = iter.into_storage();
+    /// let vec = HeapVec::<i32>::with_storage(storage);
+    /// assert!(vec.is_empty());
+    /// assert!(vec.capacity() >= 10);
+    /// ```
+    pub fn into_storage(self) -> S {
+        let mut this = ManuallyDrop::new(self);
+        let index = this.index;
+
+        // Safety
+        //
+        // `this` is never dropped, so the vector is only taken once
+        let mut vec = unsafe { ManuallyDrop::take(&mut this.vec) };
+        let len = vec.len();
+
+        // Safety
+        //
+        // * the elements before `index` were already moved out, and the elements
+        //   in `index..len` are still valid, and should be dropped
+        // * the length is set to zero first, so if dropping an element panics,
+        //   only the storage will be dropped
+        unsafe {
+            vec.set_len_unchecked(0);
+            vec.storage.__on_drop__(0);
+            let ptr = vec.as_mut_ptr().add(index);
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(ptr, len.wrapping_sub(index)));
+            vec.storage.__wipe__(0, len);
+        }
+
+        vec.into_raw_parts().1
+    }
+
+    /// Move the remaining elements in the iterator to the front of the storage,
+    /// and return them as a vector
+    ///
+    /// ```rust
+    /// # use generic_vec::HeapVec;
+    /// let vec: HeapVec<i32> = (0..5).collect();
+    /// let mut iter = vec.into_iter();
+    /// assert_eq!(iter.next(), Some(0));
+    /// assert_eq!(iter.next_back(), Some(4));
+    ///
+    /// let vec = iter.into_vec();
+    /// assert_eq!(vec, [1, 2, 3]);
+    /// ```
+    pub fn into_vec(self) -> GenericVec<T, S> {
+        let mut this = ManuallyDrop::new(self);
+        let index = this.index;
+
+        // Safety
+        //
+        // `this` is never dropped, so the vector is only taken once
+        let mut vec = unsafe { ManuallyDrop::take(&mut this.vec) };
+        let len = vec.len();
+        let remaining = len.wrapping_sub(index);
+
+        // Safety
+        //
+        // * the elements before `index` were already moved out, so they
+        //   can be overwritten by the remaining elements
+        // * the elements in `remaining..len` are now either moved out, or
+        //   duplicates of the remaining elements, so they must not be dropped
+        unsafe {
+            let ptr = vec.as_mut_ptr();
+            ptr::copy(ptr.add(index), ptr, remaining);
+            vec.set_len_unchecked(remaining);
+            vec.storage.__wipe__(remaining, index);
+        }
+
+        vec
+    }
+}
+
 impl<T, S: ?Sized + Storage<T>> Iterator for IntoIter<T, S> {
     type Item = T;
 
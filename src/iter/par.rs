@@ -0,0 +1,388 @@
+use crate::{
+    iter::RawCursor,
+    raw::{Storage, StorageWithCapacity},
+    GenericVec,
+};
+
+use core::{iter::FusedIterator, marker::PhantomData, ops::RangeBounds, ptr};
+use rayon::iter::{
+    plumbing::{bridge, Consumer, Folder, Producer, ProducerCallback, Reducer, UnindexedConsumer},
+    FromParallelIterator, IndexedParallelIterator, IntoParallelIterator, ParallelDrainRange, ParallelExtend,
+    ParallelIterator,
+};
+use std::{collections::LinkedList, vec::Vec};
+
+/// This struct is created by [`GenericVec::into_par_iter`](rayon::iter::IntoParallelIterator::into_par_iter).
+/// See its documentation for more.
+pub struct ParIntoIter<T, S: Storage<T>> {
+    vec: GenericVec<T, S>,
+}
+
+/// This struct is created by [`GenericVec::par_drain`](crate::GenericVec::par_drain).
+/// See its documentation for more.
+pub struct ParDrain<'a, T, S: ?Sized + Storage<T>> {
+    raw: RawCursor<'a, T, S>,
+}
+
+impl<'a, T, S: ?Sized + Storage<T>> ParDrain<'a, T, S> {
+    pub(crate) fn new(raw: RawCursor<'a, T, S>) -> Self { Self { raw } }
+}
+
+impl<T, S: ?Sized + Storage<T>> Drop for ParDrain<'_, T, S> {
+    fn drop(&mut self) {
+        // if we were never driven, then the drained range still needs to be dropped
+        // otherwise, the producer took ownership of all of the elements
+        unsafe { self.raw.drop_n_front(self.raw.len()) }
+    }
+}
+
+impl<T: Send, S: Storage<T>> IntoParallelIterator for GenericVec<T, S>
+where
+    S: Send,
+{
+    type Iter = ParIntoIter<T, S>;
+    type Item = T;
+
+    fn into_par_iter(self) -> Self::Iter { ParIntoIter { vec: self } }
+}
+
+impl<'a, T: Sync + 'a, S: ?Sized + Storage<T>> IntoParallelIterator for &'a GenericVec<T, S> {
+    type Iter = rayon::slice::Iter<'a, T>;
+    type Item = &'a T;
+
+    fn into_par_iter(self) -> Self::Iter { self.as_slice().into_par_iter() }
+}
+
+impl<'a, T: Send + 'a, S: ?Sized + Storage<T>> IntoParallelIterator for &'a mut GenericVec<T, S> {
+    type Iter = rayon::slice::IterMut<'a, T>;
+    type Item = &'a mut T;
+
+    fn into_par_iter(self) -> Self::Iter { self.as_mut_slice().into_par_iter() }
+}
+
+impl<'a, T: Send, S: ?Sized + Storage<T> + Send> ParallelDrainRange<usize> for &'a mut GenericVec<T, S> {
+    type Iter = ParDrain<'a, T, S>;
+    type Item = T;
+
+    fn par_drain<R: RangeBounds<usize>>(self, range: R) -> Self::Iter { GenericVec::par_drain(self, range) }
+}
+
+impl<T: Send, S: Storage<T> + Send> ParallelIterator for ParIntoIter<T, S> {
+    type Item = T;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> { Some(self.vec.len()) }
+}
+
+impl<T: Send, S: Storage<T> + Send> IndexedParallelIterator for ParIntoIter<T, S> {
+    fn drive<C>(self, consumer: C) -> C::Result
+    where
+        C: Consumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn len(&self) -> usize { self.vec.len() }
+
+    fn with_producer<CB>(mut self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        // drain every element, then the storage only needs to clean itself up
+        self.vec.par_drain(..).with_producer(callback)
+    }
+}
+
+impl<T: Send, S: ?Sized + Storage<T> + Send> ParallelIterator for ParDrain<'_, T, S> {
+    type Item = T;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> { Some(self.raw.len()) }
+}
+
+impl<T: Send, S: ?Sized + Storage<T> + Send> IndexedParallelIterator for ParDrain<'_, T, S> {
+    fn drive<C>(self, consumer: C) -> C::Result
+    where
+        C: Consumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn len(&self) -> usize { self.raw.len() }
+
+    fn with_producer<CB>(mut self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        // Safety
+        //
+        // * we take all of the remaining elements in the `RawCursor`
+        // * the producer takes ownership of these elements, and will
+        //   read or drop each of them exactly once (even on panic)
+        // * when `self` is dropped, the `RawCursor` will move the tail
+        //   of the vector into the gap
+        let len = self.raw.len();
+        let producer = unsafe { DrainProducer::new(&mut *self.raw.take_n_front(len)) };
+        callback.callback(producer)
+    }
+}
+
+struct DrainProducer<'a, T> {
+    slice: &'a mut [T],
+}
+
+impl<'a, T> DrainProducer<'a, T> {
+    /// # Safety
+    ///
+    /// The `DrainProducer` takes ownership of all elements in the slice
+    unsafe fn new(slice: &'a mut [T]) -> Self { Self { slice } }
+}
+
+impl<T> Drop for DrainProducer<'_, T> {
+    fn drop(&mut self) {
+        let slice: *mut [T] = core::mem::take::<&mut [T]>(&mut self.slice);
+        unsafe { ptr::drop_in_place(slice) }
+    }
+}
+
+impl<'a, T: Send> Producer for DrainProducer<'a, T> {
+    type IntoIter = SliceDrain<'a, T>;
+    type Item = T;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        SliceDrain {
+            iter: core::mem::take(&mut self.slice).iter_mut(),
+        }
+    }
+
+    fn split_at(mut self, index: usize) -> (Self, Self) {
+        let (left, right) = core::mem::take(&mut self.slice).split_at_mut(index);
+        unsafe { (Self::new(left), Self::new(right)) }
+    }
+}
+
+struct SliceDrain<'a, T> {
+    iter: core::slice::IterMut<'a, T>,
+}
+
+impl<T> Drop for SliceDrain<'_, T> {
+    fn drop(&mut self) {
+        let iter = core::mem::replace(&mut self.iter, [].iter_mut());
+        unsafe { ptr::drop_in_place(iter.into_slice()) }
+    }
+}
+
+impl<T> Iterator for SliceDrain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let ptr: *const T = self.iter.next()?;
+        Some(unsafe { ptr.read() })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) { self.iter.size_hint() }
+}
+
+impl<T> DoubleEndedIterator for SliceDrain<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let ptr: *const T = self.iter.next_back()?;
+        Some(unsafe { ptr.read() })
+    }
+}
+
+impl<T> ExactSizeIterator for SliceDrain<'_, T> {}
+impl<T> FusedIterator for SliceDrain<'_, T> {}
+
+impl<T: Send, S: StorageWithCapacity<T>> FromParallelIterator<T> for GenericVec<T, S> {
+    fn from_par_iter<I: IntoParallelIterator<Item = T>>(par_iter: I) -> Self {
+        let mut vec = Self::default();
+        vec.par_extend(par_iter);
+        vec
+    }
+}
+
+impl<T: Send, S: ?Sized + Storage<T>> ParallelExtend<T> for GenericVec<T, S> {
+    fn par_extend<I: IntoParallelIterator<Item = T>>(&mut self, par_iter: I) {
+        let par_iter = par_iter.into_par_iter();
+
+        match par_iter.opt_len() {
+            // `opt_len` only returns `Some` for indexed iterators, so the
+            // iterator will drive the `CollectConsumer` as an indexed consumer
+            Some(len) => {
+                self.reserve(len);
+
+                let start = SendPtr(unsafe { self.as_mut_ptr().add(self.len()) });
+                let result = par_iter.drive_unindexed(CollectConsumer {
+                    start,
+                    len,
+                    mark: PhantomData,
+                });
+
+                assert_eq!(
+                    result.init_len, len,
+                    "Expected {} elements from the parallel iterator, but got {}",
+                    len, result.init_len
+                );
+
+                // Safety
+                //
+                // * we reserved enough space for `len` elements
+                // * the `CollectResult` covers exactly the `len` elements
+                //   after the end of the vector, so they are all initialized
+                let new_len = self.len() + result.release();
+                unsafe { self.set_len_unchecked(new_len) }
+            }
+            None => {
+                let list = par_iter
+                    .fold(Vec::new, |mut vec, item| {
+                        vec.push(item);
+                        vec
+                    })
+                    .map(|vec| {
+                        let mut list = LinkedList::new();
+                        list.push_back(vec);
+                        list
+                    })
+                    .reduce(LinkedList::new, |mut left, mut right| {
+                        left.append(&mut right);
+                        left
+                    });
+
+                self.reserve(list.iter().map(Vec::len).sum());
+
+                for mut vec in list {
+                    // Safety
+                    //
+                    // * we reserved enough space for all the elements in the list
+                    // * we set the `vec`'s length to zero, so they are moved into
+                    //   `self`, and won't be dropped by `vec`
+                    unsafe {
+                        self.extend_from_slice_unchecked(&vec);
+                        vec.set_len(0);
+                    }
+                }
+            }
+        }
+    }
+}
+
+struct SendPtr<T>(*mut T);
+
+impl<T> Clone for SendPtr<T> {
+    fn clone(&self) -> Self { *self }
+}
+
+impl<T> Copy for SendPtr<T> {}
+
+unsafe impl<T: Send> Send for SendPtr<T> {}
+unsafe impl<T: Send> Sync for SendPtr<T> {}
+
+/// Writes elements directly into the spare capacity of a [`GenericVec`]
+struct CollectConsumer<'a, T> {
+    start: SendPtr<T>,
+    len: usize,
+    mark: PhantomData<&'a mut [T]>,
+}
+
+/// The initialized prefix of a `CollectConsumer`'s slot, this drops all
+/// the elements it initialized unless `release` is called
+struct CollectResult<'a, T> {
+    start: SendPtr<T>,
+    len: usize,
+    init_len: usize,
+    mark: PhantomData<&'a mut [T]>,
+}
+
+impl<T> CollectResult<'_, T> {
+    fn release(mut self) -> usize { core::mem::replace(&mut self.init_len, 0) }
+}
+
+impl<T> Drop for CollectResult<'_, T> {
+    fn drop(&mut self) { unsafe { ptr::slice_from_raw_parts_mut(self.start.0, self.init_len).drop_in_place() } }
+}
+
+impl<'a, T: Send> Consumer<T> for CollectConsumer<'a, T> {
+    type Folder = CollectResult<'a, T>;
+    type Reducer = CollectReducer;
+    type Result = CollectResult<'a, T>;
+
+    fn split_at(self, index: usize) -> (Self, Self, Self::Reducer) {
+        assert!(index <= self.len);
+
+        let left = CollectConsumer {
+            start: self.start,
+            len: index,
+            mark: PhantomData,
+        };
+        let right = CollectConsumer {
+            start: SendPtr(unsafe { self.start.0.add(index) }),
+            len: self.len - index,
+            mark: PhantomData,
+        };
+
+        (left, right, CollectReducer)
+    }
+
+    fn into_folder(self) -> Self::Folder {
+        CollectResult {
+            start: self.start,
+            len: self.len,
+            init_len: 0,
+            mark: PhantomData,
+        }
+    }
+
+    fn full(&self) -> bool { false }
+}
+
+impl<T: Send> UnindexedConsumer<T> for CollectConsumer<'_, T> {
+    fn split_off_left(&self) -> Self { unreachable!("`CollectConsumer` can only be used as an indexed consumer") }
+
+    fn to_reducer(&self) -> Self::Reducer { CollectReducer }
+}
+
+impl<T> Folder<T> for CollectResult<'_, T> {
+    type Result = Self;
+
+    fn consume(mut self, item: T) -> Self {
+        assert!(self.init_len < self.len, "Too many elements given to `CollectConsumer`");
+
+        unsafe {
+            self.start.0.add(self.init_len).write(item);
+        }
+        self.init_len += 1;
+
+        self
+    }
+
+    fn complete(self) -> Self::Result { self }
+
+    fn full(&self) -> bool { false }
+}
+
+struct CollectReducer;
+
+impl<'a, T> Reducer<CollectResult<'a, T>> for CollectReducer {
+    fn reduce(self, mut left: CollectResult<'a, T>, right: CollectResult<'a, T>) -> CollectResult<'a, T> {
+        // only merge adjacent results, if they aren't adjacent then `right` is dropped
+        // and the total length will come up short, which is caught in `par_extend`
+        if unsafe { left.start.0.add(left.init_len) } == right.start.0 {
+            left.len += right.len;
+            left.init_len += right.release();
+        }
+
+        left
+    }
+}
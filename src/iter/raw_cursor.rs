@@ -87,7 +87,9 @@ impl<'a, T, S: ?Sized + Storage<T>> RawCursor<'a, T, S> {
                     self.write_front.copy_from(self.write_back, back_len);
                 }
 
-                self.vec.as_mut().set_len_unchecked(len);
+                let vec = self.vec.as_mut();
+                vec.set_len_unchecked(len);
+                vec.storage.__wipe__(len, self.old_vec_len.wrapping_sub(len));
             }
         }
     }
@@ -101,6 +103,14 @@ impl<'a, T, S: ?Sized + Storage<T>> RawCursor<'a, T, S> {
         }
     }
 
+    /// The number of unfilled slots that can be reserved
+    /// without growing the underlying storage
+    pub(crate) fn spare_capacity(&self) -> usize {
+        let capacity = unsafe { self.vec.as_ref().capacity() };
+        let len = self.old_vec_len - self.write_len();
+        capacity.saturating_sub(len)
+    }
+
     /// Get a mutable reference to the underlying vector
     pub(crate) unsafe fn vec_mut(&mut self) -> &mut GenericVec<T, S> { unsafe { self.vec.as_mut() } }
 
@@ -296,6 +306,42 @@ impl<'a, T, S: ?Sized + Storage<T>> RawCursor<'a, T, S> {
         }
     }
 
+    /// Removes the next `n` elements of the `RawCursor`
+    /// from the underlying [`GenericVec`], and returns a pointer to them
+    ///
+    /// Advances the `RawCursor` by `n` elements
+    ///
+    /// Creates `n` unfilled slots at the front of the `RawCursor`.
+    ///
+    /// # Safety
+    ///
+    /// * The `RawCursor`'s length must be at least equal to `n`
+    /// * The caller takes ownership of the elements in the returned slice,
+    ///   and must read or drop each of them exactly once
+    #[inline]
+    #[cfg(feature = "rayon")]
+    pub(crate) unsafe fn take_n_front(&mut self, n: usize) -> *mut [T] {
+        debug_assert!(
+            self.len() >= n,
+            "Cannot take {} elements from a `RawCursor` of length {}",
+            n,
+            self.len()
+        );
+
+        unsafe {
+            let ptr = if Self::IS_ZS {
+                self.read_front = (self.read_front as usize).wrapping_add(n) as _;
+                Self::ZS_PTR
+            } else {
+                let read_front = self.read_front;
+                self.read_front = self.read_front.add(n);
+                read_front
+            };
+
+            core::ptr::slice_from_raw_parts_mut(ptr, n)
+        }
+    }
+
     /// Drops the next element of the `RawCursor`
     /// and removes them it the underlying [`GenericVec`]
     ///
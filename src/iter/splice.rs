@@ -2,6 +2,8 @@ use crate::{iter::RawCursor, Storage};
 
 use core::mem::ManuallyDrop;
 
+const INIT_GAP: usize = 4;
+
 /// This struct is created by [`GenericVec::splice`](crate::GenericVec::splice).
 /// See its documentation for more.
 pub struct Splice<'a, T, S, I>
@@ -42,48 +44,39 @@ impl<T, S: ?Sized + Storage<T>, I: Iterator<Item = T>> Drop for Splice<'_, T, S,
             return
         }
 
-        while !raw.is_write_empty() {
-            match replace_with.next() {
-                Some(value) => unsafe { raw.write_front(value) },
-                None => return,
-            }
-        }
-
-        #[cfg(not(feature = "alloc"))]
-        {
-            const CAPACITY: usize = 16;
-
-            let mut buffer = crate::uninit_array!(CAPACITY);
-            let mut buffer = crate::SliceVec::new(&mut buffer);
-
-            replace_with.for_each(|item| unsafe {
-                buffer.push_unchecked(item);
+        // the number of slots opened past the original range
+        let mut extra = 0;
 
-                if buffer.is_full() {
-                    unsafe {
-                        raw.reserve(buffer.len());
-                        raw.write_slice_front(&buffer);
-                        buffer.set_len_unchecked(0);
-                    }
+        loop {
+            while !raw.is_write_empty() {
+                match replace_with.next() {
+                    Some(value) => unsafe { raw.write_front(value) },
+                    None => return,
                 }
-            });
-
-            unsafe {
-                raw.reserve(buffer.len());
-                raw.write_slice_front(&buffer);
-                core::mem::forget(buffer);
             }
-        }
 
-        #[cfg(feature = "alloc")]
-        {
-            let mut temp: std::vec::Vec<T> = replace_with.collect();
+            let value = match replace_with.next() {
+                Some(value) => value,
+                None => return,
+            };
+
+            let space = match replace_with.size_hint() {
+                // the rest of the items are known, so open exactly enough space for them
+                (lower, Some(upper)) if lower == upper => upper.saturating_add(1),
+                // otherwise grow geometrically, but don't force the storage to grow
+                // any more than it has to
+                (lower, _) => {
+                    let space = match raw.spare_capacity() {
+                        0 => extra.max(INIT_GAP),
+                        spare => extra.max(INIT_GAP).min(spare),
+                    };
+                    space.max(lower.saturating_add(1))
+                }
+            };
 
-            unsafe {
-                raw.reserve(temp.len());
-                raw.write_slice_front(&temp);
-                temp.set_len(0);
-            }
+            extra += space;
+            raw.reserve(space);
+            unsafe { raw.write_front(value) }
         }
     }
 }
@@ -36,7 +36,14 @@
 //! * `std` (default) - enables you to use an allocator, and
 //! * `alloc` - enables you to use an allocator, for heap allocated storages
 //!     (like [`Vec`])
-//! * `nightly` - enables you to use array (`[T; N]`) based storages
+//! * `nightly` - enables `const fn`s, custom allocators for [`HeapVec`], and some optimizations
+//! * `rayon` - enables parallel iterators for [`GenericVec`] using [`rayon`](https://docs.rs/rayon)
+//! * `mmap` - enables [`raw::MmapStorage`] and [`MmapVec`], file backed storages,
+//!   [`raw::Reserved`], an address-stable storage, and [`raw::AnonMmap`], a heap-like
+//!   storage that grows with `mremap` (Linux only)
+//! * `zeroize` - enables [`raw::Zeroizing`] and [`GenericVec::zeroize`] using [`zeroize`](https://docs.rs/zeroize)
+//! * `debug-checks` - enables [`raw::Checked`], and always checks the preconditions of the
+//!   `*_unchecked` methods, even in release mode
 //!
 //! # Basic Usage
 //!
@@ -113,14 +120,11 @@
 //! a new [`ArrayVec`](type@ArrayVec) without passing in an existing buffer,
 //! unlike the slice versions.
 //!
-//! On stable, you can use the [`ArrayVec`](macro@ArrayVec) or
-//! [`InitArrayVec`](macro@InitArrayVec) to construct the type. On `nightly`,
-//! you can use the type aliases [`ArrayVec`](type@ArrayVec) and
-//! [`InitArrayVec`](type@InitArrayVec). The macros will be deprecated once
-//! `min_const_generics` hits stable.
-//!
-//! The only limitation on stable is that you can only use [`InitArrayVec`](type@InitArrayVec)
-//! capacity up to 32. i.e. `InitArrayVec![i32; 33]` doesn't work. `ArrayVec` does not suffer
+//! Arrays of any length can be used as storages. On compilers older than Rust 1.51,
+//! which don't have `min_const_generics`, the type aliases aren't available. Instead
+//! you can use the [`ArrayVec`](macro@ArrayVec) or [`InitArrayVec`](macro@InitArrayVec)
+//! macros to construct the type, but [`InitArrayVec`](type@InitArrayVec)'s capacity is
+//! limited to 32. i.e. `InitArrayVec![i32; 33]` doesn't work. `ArrayVec` does not suffer
 //! from this limitation because it is built atop [`TypeVec`].
 //!
 //! ```rust
@@ -175,8 +179,8 @@
 //! ## `nightly`
 //!
 //! On `nightly`
-//! * the restriction on [`InitArrayVec`](type@InitArrayVec)'s length goes away.
 //! * many functions/methods become `const fn`s
+//! * [`HeapVec`] can use any [`Allocator`](std::alloc::Allocator)
 //! * a number of optimizations are enabled
 //! * some diagnostics become better
 //!
@@ -196,15 +200,38 @@ use core::{
     ptr,
 };
 
+/// Check a precondition of an unsafe function
+///
+/// This is a `debug_assert!`, unless the `debug-checks` feature is enabled,
+/// then it's always checked
+macro_rules! debug_check {
+    ($($args:tt)*) => {
+        if cfg!(feature = "debug-checks") {
+            assert!($($args)*)
+        } else {
+            debug_assert!($($args)*)
+        }
+    };
+}
+
 mod extension;
 mod impls;
 mod slice;
+mod slice_vec;
+#[cfg(any(doc, feature = "alloc"))]
+mod std_vec;
 
 pub mod iter;
 pub mod raw;
 
 use raw::Storage;
 
+pub use slice_vec::SliceVecGuard;
+
+#[cfg(any(doc, feature = "alloc"))]
+#[cfg_attr(doc, doc(cfg(feature = "alloc")))]
+pub use std_vec::StdVecView;
+
 #[doc(hidden)]
 pub use core;
 
@@ -218,21 +245,31 @@ pub type HeapVec<T, A = std::alloc::Global> = GenericVec<T, raw::Heap<T, A>>;
 #[cfg_attr(doc, doc(cfg(feature = "alloc")))]
 pub type HeapVec<T> = GenericVec<T, raw::Heap<T>>;
 
+/// A heap backed vector with a growable capacity, that is aligned to at least `ALIGN`
+#[cfg(any(doc, all(feature = "alloc", not(no_const_generics))))]
+#[cfg_attr(doc, doc(cfg(feature = "alloc")))]
+pub type AlignedHeapVec<T, const ALIGN: usize> = GenericVec<T, raw::AlignedHeap<T, ALIGN>>;
+
+/// A vector backed by a memory mapped file
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+#[cfg_attr(doc, doc(cfg(all(feature = "mmap", target_os = "linux"))))]
+pub type MmapVec<T> = GenericVec<T, raw::MmapStorage<T>>;
+
 /// An array backed vector backed by potentially uninitialized memory
-#[cfg(any(doc, feature = "nightly"))]
-#[cfg_attr(doc, doc(cfg(feature = "nightly")))]
+#[cfg(any(doc, not(no_const_generics)))]
 pub type ArrayVec<T, const N: usize> = TypeVec<T, [T; N]>;
 /// An slice backed vector backed by potentially uninitialized memory
 pub type SliceVec<'a, T> = GenericVec<T, &'a mut raw::UninitSlice<T>>;
 
 /// An array backed vector backed by initialized memory
-#[cfg(any(doc, feature = "nightly"))]
-#[cfg_attr(doc, doc(cfg(feature = "nightly")))]
+#[cfg(any(doc, not(no_const_generics)))]
 pub type InitArrayVec<T, const N: usize> = GenericVec<T, [T; N]>;
 /// An slice backed vector backed by initialized memory
 pub type InitSliceVec<'a, T> = GenericVec<T, &'a mut [T]>;
 /// A counter vector that can only store zero-sized types
 pub type ZSVec<T> = GenericVec<T, raw::ZeroSized<T>>;
+/// A vector backed by any storage, see [`DynStorage`](raw::DynStorage) for details
+pub type DynVec<'a, T> = GenericVec<T, dyn raw::DynStorage<T> + 'a>;
 /// An type based vector backed by uninitialized memory with the same layout as `B`
 ///
 /// see: [`UninitBuffer`](raw::UninitBuffer) for details
@@ -257,8 +294,16 @@ pub mod macros {
 /// let x: ArrayVec<i32, 2> = gvec![0, 1];
 /// assert_eq!(x, [0, 1]);
 /// ```
+///
+/// If the storage has a fixed capacity, listing more elements than
+/// fit is a compile error
+///
+/// ```compile_fail
+/// # use generic_vec::{gvec, ArrayVec};
+/// let x: ArrayVec<i32, 2> = gvec![0, 1, 2];
+/// ```
 #[macro_export]
-#[cfg(feature = "nightly")]
+#[cfg(not(no_const_generics))]
 macro_rules! gvec {
     ($expr:expr; $n:expr) => {{
         let len = $n;
@@ -269,7 +314,7 @@ macro_rules! gvec {
     ($($expr:expr),*) => {{
         let expr = [$($expr),*];
         let mut vec = $crate::GenericVec::with_capacity(expr.len());
-        unsafe { vec.push_array_unchecked(expr); }
+        unsafe { vec.__push_array_const_checked(expr); }
         vec
     }};
 }
@@ -293,7 +338,7 @@ macro_rules! count {
 /// assert_eq!(x, [1, 2, 3, 4]);
 /// ```
 #[macro_export]
-#[cfg(not(feature = "nightly"))]
+#[cfg(no_const_generics)]
 macro_rules! gvec {
     ($expr:expr; $n:expr) => {{
         let len = $n;
@@ -351,9 +396,21 @@ macro_rules! save_spare {
     }};
 }
 
+/// Fails to compile if an array of `N` elements can never fit in a vector backed by `S`
+#[cfg(not(no_const_generics))]
+struct ArrayFits<T, S: ?Sized, const N: usize>(PhantomData<T>, PhantomData<S>);
+
+#[cfg(not(no_const_generics))]
+impl<T, S: ?Sized + Storage<T>, const N: usize> ArrayFits<T, S, N> {
+    const CHECK: () = [()][match S::CONST_CAPACITY {
+        Some(capacity) => (capacity < N) as usize,
+        None => 0,
+    }];
+}
+
 #[doc(hidden)]
 pub fn validate_spare<T>(spare_ptr: *const T, orig: &[T]) {
-    debug_assert!(
+    debug_check!(
         unsafe { orig.as_ptr().add(orig.len()) == spare_ptr },
         "Tried to use `save_spare!` with a `SliceVec` that was not obtained from `GenricVec::spare_capacity_mut`. \
          This is undefined behavior on release mode!"
@@ -362,7 +419,8 @@ pub fn validate_spare<T>(spare_ptr: *const T, orig: &[T]) {
 
 /// An array backed vector backed by potentially uninitialized memory
 ///
-/// On `nightly`, it's prefered to use the [`ArrayVec`](type@ArrayVec) type alias
+/// It's prefered to use the [`ArrayVec`](type@ArrayVec) type alias, this macro
+/// is only needed on compilers older than Rust 1.51
 #[macro_export]
 macro_rules! ArrayVec {
     ($type:ty; $len:expr) => {
@@ -372,7 +430,8 @@ macro_rules! ArrayVec {
 
 /// An array backed vector backed by initialized memory
 ///
-/// On `nightly`, it's prefered to use the [`InitArrayVec`](type@InitArrayVec) type alias
+/// It's prefered to use the [`InitArrayVec`](type@InitArrayVec) type alias, this macro
+/// is only needed on compilers older than Rust 1.51
 #[macro_export]
 macro_rules! InitArrayVec {
     ($type:ty; $len:expr) => {
@@ -413,9 +472,13 @@ impl<T, S: ?Sized + Storage<T>> Drop for GenericVec<T, S> {
     fn drop(&mut self) {
         // The first `len` elements are guaranteed to be initialized
         // as part of the guarantee on `self.set_len_unchecked`
-        // These elements should be dropped when the `GenericVec` gets dropped/
-        // The storage will clean it's self up on drop
-        unsafe { ptr::drop_in_place(self.as_mut_slice()) }
+        // These elements should be dropped when the `GenericVec` gets dropped,
+        // unless the storage took them. The storage will clean it's self up on drop
+        unsafe {
+            if !self.storage.__on_drop__(self.len) {
+                ptr::drop_in_place(self.as_mut_slice())
+            }
+        }
     }
 }
 
@@ -456,6 +519,62 @@ impl<T, S: raw::StorageWithCapacity<T>> GenericVec<T, S> {
     /// Create a new empty `GenericVec` with the backend with at least the given capacity
     pub fn with_capacity(capacity: usize) -> Self { Self::with_storage(S::with_capacity(capacity)) }
 
+    /// Create a new `GenericVec` with `len` elements, where each element is `f(index)`
+    ///
+    /// ```rust
+    /// # use generic_vec::ArrayVec;
+    /// let vec = ArrayVec::<usize, 8>::from_fn(4, |i| i * i);
+    /// assert_eq!(vec, [0, 1, 4, 9]);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// May panic or abort if it isn't possible to allocate enough space for `len` elements
+    ///
+    /// # Panic behavior
+    ///
+    /// If `f` panics, then all created elements will be dropped
+    pub fn from_fn<F: FnMut(usize) -> T>(len: usize, f: F) -> Self { Self::from_fn_in(S::with_capacity(len), len, f) }
+
+    /// Create a new `GenericVec` with `len` elements, where each element is `f(index)`,
+    /// returns `None` if it isn't possible to allocate enough space for `len` elements
+    ///
+    /// ```rust
+    /// # use generic_vec::ArrayVec;
+    /// assert!(ArrayVec::<usize, 2>::try_from_fn(4, |i| i).is_none());
+    /// assert_eq!(ArrayVec::<usize, 4>::try_from_fn(4, |i| i).unwrap(), [0, 1, 2, 3]);
+    /// ```
+    ///
+    /// # Panic behavior
+    ///
+    /// If `f` panics, then all created elements will be dropped
+    pub fn try_from_fn<F: FnMut(usize) -> T>(len: usize, f: F) -> Option<Self> {
+        Self::try_from_fn_in(S::default(), len, f).ok()
+    }
+
+    /// Clone the `GenericVec` by copying its elements
+    ///
+    /// This is the same as [`Clone::clone`], but it doesn't need to call `T::clone`,
+    /// so it's a single `memcpy`.
+    ///
+    /// ```rust
+    /// # use generic_vec::ArrayVec;
+    /// let vec = ArrayVec::<i32, 4>::from([1, 2, 3]);
+    /// assert_eq!(vec.clone_copy(), [1, 2, 3]);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// May panic or abort if it isn't possible to allocate enough space for the elements
+    pub fn clone_copy(&self) -> Self
+    where
+        T: Copy,
+    {
+        let mut vec = Self::with_capacity(self.len());
+        vec.extend_from_copy_slice(self);
+        vec
+    }
+
     #[inline]
     #[allow(non_snake_case)]
     fn __with_capacity__const_capacity_checked(capacity: usize, old_capacity: Option<usize>) -> Self {
@@ -463,6 +582,235 @@ impl<T, S: raw::StorageWithCapacity<T>> GenericVec<T, S> {
     }
 }
 
+impl<T, S: Storage<T>> GenericVec<T, S> {
+    /// Create a new `GenericVec` with the given backend, with `len` elements, where each element is `f(index)`
+    ///
+    /// ```rust
+    /// use generic_vec::{GenericVec, raw::UninitBuffer};
+    ///
+    /// let vec = GenericVec::from_fn_in(UninitBuffer::<[u8; 8]>::uninit(), 3, |i| i as u8 + b'a');
+    /// assert_eq!(vec, *b"abc");
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// May panic or reallocate if the storage can't hold `len` elements
+    ///
+    /// # Panic behavior
+    ///
+    /// If `f` panics, then all created elements will be dropped
+    pub fn from_fn_in<F: FnMut(usize) -> T>(storage: S, len: usize, mut f: F) -> Self {
+        let mut vec = Self::with_storage(storage);
+        let mut index = 0;
+
+        vec.grow_with(len, || {
+            let value = f(index);
+            index += 1;
+            value
+        });
+
+        vec
+    }
+
+    /// Create a new `GenericVec` with the given backend, with `len` elements, where each element is `f(index)`
+    ///
+    /// Returns the storage if it can't hold `len` elements, without calling `f`
+    ///
+    /// # Panic behavior
+    ///
+    /// If `f` panics, then all created elements will be dropped
+    pub fn try_from_fn_in<F: FnMut(usize) -> T>(mut storage: S, len: usize, f: F) -> Result<Self, S> {
+        if storage.try_reserve(len) {
+            Ok(Self::from_fn_in(storage, len, f))
+        } else {
+            Err(storage)
+        }
+    }
+
+    /// Create a new `GenericVec` with the given backend, with clones of the elements of `slice`
+    ///
+    /// For `Copy` types, [`GenericVec::from_copy_slice_in`] is a single `memcpy`
+    ///
+    /// ```rust
+    /// use generic_vec::{GenericVec, raw::UninitBuffer};
+    ///
+    /// let vec = GenericVec::from_slice_in(UninitBuffer::<[i32; 4]>::uninit(), &[1, 2, 3]);
+    /// assert_eq!(vec, [1, 2, 3]);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// May panic or reallocate if the storage can't hold `slice.len()` elements
+    ///
+    /// # Panic behavior
+    ///
+    /// If `T::clone` panics, then all created elements will be dropped
+    pub fn from_slice_in(storage: S, slice: &[T]) -> Self
+    where
+        T: Clone,
+    {
+        let mut vec = Self::with_storage(storage);
+        vec.extend_from_slice(slice);
+        vec
+    }
+
+    /// Create a new `GenericVec` with the given backend, with clones of the elements of `slice`
+    ///
+    /// Returns the storage if it can't hold `slice.len()` elements
+    ///
+    /// ```rust
+    /// use generic_vec::{GenericVec, raw::UninitBuffer};
+    ///
+    /// assert!(GenericVec::try_from_slice_in(UninitBuffer::<[i32; 2]>::uninit(), &[1, 2, 3]).is_err());
+    /// ```
+    ///
+    /// # Panic behavior
+    ///
+    /// If `T::clone` panics, then all created elements will be dropped
+    pub fn try_from_slice_in(mut storage: S, slice: &[T]) -> Result<Self, S>
+    where
+        T: Clone,
+    {
+        if storage.try_reserve(slice.len()) {
+            Ok(Self::from_slice_in(storage, slice))
+        } else {
+            Err(storage)
+        }
+    }
+
+    /// Create a new `GenericVec` with the given backend, with copies of the elements of `slice`
+    ///
+    /// This is the same as [`GenericVec::from_slice_in`], but it doesn't need to call
+    /// `T::clone`, so it's a single `memcpy`.
+    ///
+    /// ```rust
+    /// use generic_vec::{GenericVec, raw::UninitBuffer};
+    ///
+    /// let vec = GenericVec::from_copy_slice_in(UninitBuffer::<[i32; 4]>::uninit(), &[1, 2, 3]);
+    /// assert_eq!(vec, [1, 2, 3]);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// May panic or reallocate if the storage can't hold `slice.len()` elements
+    pub fn from_copy_slice_in(storage: S, slice: &[T]) -> Self
+    where
+        T: Copy,
+    {
+        let mut vec = Self::with_storage(storage);
+        vec.extend_from_copy_slice(slice);
+        vec
+    }
+
+    /// Create a new `GenericVec` with the given backend, with copies of the elements of `slice`
+    ///
+    /// Returns the storage if it can't hold `slice.len()` elements
+    pub fn try_from_copy_slice_in(mut storage: S, slice: &[T]) -> Result<Self, S>
+    where
+        T: Copy,
+    {
+        if storage.try_reserve(slice.len()) {
+            Ok(Self::from_copy_slice_in(storage, slice))
+        } else {
+            Err(storage)
+        }
+    }
+
+    /// Create a new `GenericVec` with the given backend, with `n` clones of `value`
+    ///
+    /// For `Copy` types, [`GenericVec::from_copy_elem_in`] doesn't need to call `T::clone`
+    ///
+    /// ```rust
+    /// use generic_vec::{GenericVec, raw::UninitBuffer};
+    ///
+    /// let vec = GenericVec::from_elem_in(UninitBuffer::<[u8; 4]>::uninit(), 0xff_u8, 3);
+    /// assert_eq!(vec, [0xff; 3]);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// May panic or reallocate if the storage can't hold `n` elements
+    ///
+    /// # Panic behavior
+    ///
+    /// If `T::clone` panics, then all created elements will be dropped
+    pub fn from_elem_in(storage: S, value: T, n: usize) -> Self
+    where
+        T: Clone,
+    {
+        let mut vec = Self::with_storage(storage);
+        vec.grow(n, value);
+        vec
+    }
+
+    /// Create a new `GenericVec` with the given backend, with `n` clones of `value`
+    ///
+    /// Returns the storage if it can't hold `n` elements
+    ///
+    /// # Panic behavior
+    ///
+    /// If `T::clone` panics, then all created elements will be dropped
+    pub fn try_from_elem_in(mut storage: S, value: T, n: usize) -> Result<Self, S>
+    where
+        T: Clone,
+    {
+        if storage.try_reserve(n) {
+            Ok(Self::from_elem_in(storage, value, n))
+        } else {
+            Err(storage)
+        }
+    }
+
+    /// Create a new `GenericVec` with the given backend, with `n` copies of `value`
+    ///
+    /// This is the same as [`GenericVec::from_elem_in`], but it doesn't need to call `T::clone`.
+    ///
+    /// ```rust
+    /// use generic_vec::{GenericVec, raw::UninitBuffer};
+    ///
+    /// let vec = GenericVec::from_copy_elem_in(UninitBuffer::<[u8; 4]>::uninit(), 0xff_u8, 3);
+    /// assert_eq!(vec, [0xff; 3]);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// May panic or reallocate if the storage can't hold `n` elements
+    pub fn from_copy_elem_in(storage: S, value: T, n: usize) -> Self
+    where
+        T: Copy,
+    {
+        let mut vec = Self::with_storage(storage);
+        vec.grow_copy(n, value);
+        vec
+    }
+
+    /// Create a new `GenericVec` with the given backend, with `n` copies of `value`
+    ///
+    /// Returns the storage if it can't hold `n` elements
+    pub fn try_from_copy_elem_in(mut storage: S, value: T, n: usize) -> Result<Self, S>
+    where
+        T: Copy,
+    {
+        if storage.try_reserve(n) {
+            Ok(Self::from_copy_elem_in(storage, value, n))
+        } else {
+            Err(storage)
+        }
+    }
+
+    /// Use this vector as a vector backed by any storage
+    ///
+    /// `&mut GenericVec<T, S>` also coerces to `&mut DynVec<'_, T>`
+    /// implicitly, this method is useful when type inference needs some help.
+    pub fn as_dyn(&self) -> &DynVec<'_, T> { self }
+
+    /// Use this vector as a vector backed by any storage
+    ///
+    /// `&mut GenericVec<T, S>` also coerces to `&mut DynVec<'_, T>`
+    /// implicitly, this method is useful when type inference needs some help.
+    pub fn as_dyn_mut(&mut self) -> &mut DynVec<'_, T> { self }
+}
+
 impl<T, B> TypeVec<T, B, T> {
     /// Create a new [`TypeVec`]
     pub const fn new() -> Self { Self::with_align() }
@@ -492,13 +840,47 @@ impl<T, B, A> TypeVec<T, B, A> {
     }
 }
 
-#[cfg(any(doc, feature = "nightly"))]
-#[cfg_attr(doc, doc(cfg(feature = "nightly")))]
+#[cfg(any(doc, not(no_const_generics)))]
+impl<T, S: raw::FixedCapacity<T>> GenericVec<T, S> {
+    /// Create a new `GenericVec` with the given backend, filled with the elements of `array`
+    ///
+    /// Unlike [`GenericVec::push_array`], this is checked at compile time, so it can't panic
+    ///
+    /// ```rust
+    /// use generic_vec::{GenericVec, raw::UninitBuffer};
+    ///
+    /// let vec = GenericVec::from_array_in([1, 2, 3], UninitBuffer::<[i32; 4]>::uninit());
+    /// assert_eq!(vec, [1, 2, 3]);
+    /// ```
+    ///
+    /// Arrays that don't fit in the storage don't compile
+    ///
+    /// ```compile_fail
+    /// use generic_vec::{GenericVec, raw::UninitBuffer};
+    ///
+    /// let vec = GenericVec::from_array_in([1, 2, 3], UninitBuffer::<[i32; 2]>::uninit());
+    /// ```
+    pub fn from_array_in<const N: usize>(array: [T; N], storage: S) -> Self {
+        let mut vec = Self::with_storage(storage);
+
+        // Safety
+        //
+        // the vector is empty, and `__push_array_const_checked` checks
+        // at compile time that `N` elements fit in the storage
+        unsafe {
+            vec.__push_array_const_checked(array);
+        }
+
+        vec
+    }
+}
+
+#[cfg(any(doc, not(no_const_generics)))]
 impl<T, const N: usize> ArrayVec<T, N> {
     /// Create a new full `ArrayVec`
     pub const fn from_array(array: [T; N]) -> Self {
         Self {
-            len: 0,
+            len: N,
             mark: PhantomData,
             storage: raw::UninitBuffer::new(array),
         }
@@ -516,8 +898,7 @@ impl<T, const N: usize> ArrayVec<T, N> {
     }
 }
 
-#[cfg(feature = "nightly")]
-#[cfg_attr(doc, doc(cfg(feature = "nightly")))]
+#[cfg(any(doc, not(no_const_generics)))]
 impl<T: Copy, const N: usize> InitArrayVec<T, N> {
     /// Create a new full `InitArrayVec`
     pub fn new(storage: [T; N]) -> Self {
@@ -542,6 +923,84 @@ impl<T> HeapVec<T> {
     }
 }
 
+#[cfg(any(doc, all(feature = "alloc", not(no_const_generics))))]
+#[cfg_attr(doc, doc(cfg(feature = "alloc")))]
+impl<T, const ALIGN: usize> AlignedHeapVec<T, ALIGN> {
+    /// Create a new empty `AlignedHeapVec`
+    pub const fn new() -> Self {
+        Self {
+            len: 0,
+            mark: PhantomData,
+            storage: raw::AlignedHeap::new(),
+        }
+    }
+}
+
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+#[cfg_attr(doc, doc(cfg(all(feature = "mmap", target_os = "linux"))))]
+impl<T: Copy> MmapVec<T> {
+    /// Create a new empty `MmapVec` backed by the file at `path`,
+    /// replacing the file if it already exists
+    ///
+    /// ```rust
+    /// # let path = std::env::temp_dir().join("generic-vec-mmap-doc.bin");
+    /// use generic_vec::MmapVec;
+    ///
+    /// let mut vec = unsafe { MmapVec::<u32>::create(&path)? };
+    /// vec.extend_from_slice(&[1, 2, 3]);
+    /// vec.flush()?;
+    /// drop(vec);
+    ///
+    /// let vec = unsafe { MmapVec::<u32>::open(&path)? };
+    /// assert_eq!(vec, [1, 2, 3]);
+    /// # std::fs::remove_file(path)?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified, truncated or mapped by anything else, including
+    /// another `MmapVec` of the same file, while this vector is alive
+    pub unsafe fn create<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Self> {
+        unsafe { raw::MmapStorage::create(path).map(Self::with_storage) }
+    }
+
+    /// Open a `MmapVec` that was previously created by [`MmapVec::create`],
+    /// with the length that was last flushed
+    ///
+    /// # Safety
+    ///
+    /// * The file must contain valid `T`s for the stored length, for example
+    ///   it must have last been written by a `MmapVec<T>`
+    /// * The file must not be modified, truncated or mapped by anything else, including
+    ///   another `MmapVec` of the same file, while this vector is alive
+    pub unsafe fn open<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Self> {
+        let (storage, len) = unsafe { raw::MmapStorage::open(path)? };
+        let mut vec = Self::with_storage(storage);
+        unsafe { vec.set_len_unchecked(len) }
+        Ok(vec)
+    }
+
+    /// Store the current length in the file's header, then synchronously write
+    /// all of the elements back to the file
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.storage.set_stored_len(self.len);
+        self.storage.flush()
+    }
+
+    /// Store the current length in the file's header, then synchronously write
+    /// the header and the elements in `range` back to the file
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds of the vector
+    pub fn sync_range<R: RangeBounds<usize>>(&mut self, range: R) -> std::io::Result<()> {
+        let range = slice::check_range(self.len, range);
+        self.storage.set_stored_len(self.len);
+        self.storage.sync_range(range)
+    }
+}
+
 #[cfg(any(doc, all(feature = "nightly", feature = "alloc")))]
 #[cfg_attr(doc, doc(cfg(all(feature = "nightly", feature = "alloc"))))]
 impl<T, A: std::alloc::Allocator> HeapVec<T, A> {
@@ -651,6 +1110,51 @@ impl<T> ZSVec<T> {
     pub const fn new() -> Self { Self::NEW }
 }
 
+macro_rules! byte_vec {
+    ($($byte:ident)*) => {$(
+        impl<S: ?Sized + Storage<$byte>> GenericVec<$byte, S> {
+            /// Grows the `GenericVec` in-place by additional bytes, all set to `byte`
+            ///
+            /// This is the same as [`GenericVec::grow_copy`], but it's always a single `memset`
+            ///
+            /// # Panic
+            ///
+            /// May panic or reallocate if the collection is full
+            pub fn grow_bytes(&mut self, additional: usize, byte: $byte) {
+                self.reserve(additional);
+
+                // Safety
+                //
+                // * we reserved enough space for `additional` bytes
+                // * any byte is a valid value
+                unsafe {
+                    let len = self.len();
+                    self.as_mut_ptr().add(len).write_bytes(byte as u8, additional);
+                    self.set_len_unchecked(len.wrapping_add(additional));
+                }
+            }
+
+            /// Resizes the `GenericVec` in-place so that `len` is equal to `new_len`,
+            /// new bytes are set to `byte`
+            ///
+            /// This is the same as [`GenericVec::resize_copy`], but it's always a single `memset`
+            ///
+            /// # Panic
+            ///
+            /// May panic or reallocate if the collection is full
+            pub fn resize_bytes(&mut self, new_len: usize, byte: $byte) {
+                match new_len.checked_sub(self.len()) {
+                    Some(0) => (),
+                    Some(additional) => self.grow_bytes(additional, byte),
+                    None => self.truncate(new_len),
+                }
+            }
+        }
+    )*};
+}
+
+byte_vec! { u8 i8 }
+
 impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
     /// Returns a shared raw pointer to the vector's buffer.
     ///
@@ -688,7 +1192,19 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
     ///
     /// * new_len must be less than or equal to `capacity()`.
     /// * The elements at `old_len..new_len` must be initialized.
-    pub unsafe fn set_len_unchecked(&mut self, len: usize) { self.len = len; }
+    pub unsafe fn set_len_unchecked(&mut self, len: usize) {
+        debug_check!(
+            len <= self.capacity(),
+            "Tried to set the length to {}, past the capacity {}! This is UB in release mode",
+            len,
+            self.capacity(),
+        );
+
+        #[cfg(feature = "debug-checks")]
+        self.storage.__verify__();
+
+        self.len = len;
+    }
 
     /// Set the length of a vector
     pub fn set_len(&mut self, len: usize)
@@ -756,14 +1272,127 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
         //
         // The elements from `len..capacity` are guaranteed to be contain
         // `A::BufferItem`s, as per `Storage`'s safety requirements
-        unsafe {
+        unsafe {
+            let len = self.len();
+            let cap = self.capacity();
+            SliceVec::new(core::slice::from_raw_parts_mut(
+                self.storage.as_mut_ptr().add(len).cast(),
+                cap.wrapping_sub(len),
+            ))
+        }
+    }
+
+    /// Returns the initialized elements, and the remaining spare capacity of the vector
+    /// as a [`SliceVec<'_, T>`](SliceVec).
+    ///
+    /// This is like [`GenericVec::spare_capacity_mut`], but you can still access the
+    /// existing elements while writing to the spare capacity. Like with
+    /// [`GenericVec::spare_capacity_mut`], all elements pushed into the [`SliceVec<'_, T>`](SliceVec)
+    /// will be dropped unless they are persisted with [`save_spare`]
+    ///
+    /// ```
+    /// let mut vec = generic_vec::TypeVec::<i32, [i32; 16]>::new();
+    /// vec.extend_from_slice(&[1, 2, 3]);
+    ///
+    /// let (init, mut spare) = vec.split_at_spare_mut();
+    /// spare.extend(init.iter().map(|x| x * 10));
+    /// init[0] = 0;
+    /// unsafe { generic_vec::save_spare!(spare, &mut vec) }
+    ///
+    /// assert_eq!(vec, [0, 2, 3, 10, 20, 30]);
+    /// ```
+    pub fn split_at_spare_mut(&mut self) -> (&mut [T], SliceVec<'_, T>) {
+        // Safety
+        //
+        // * The first `len` elements are initialized, and the elements from
+        //   `len..capacity` are guaranteed to be contain `A::BufferItem`s,
+        //   as per `Storage`'s safety requirements
+        // * the two slices don't overlap
+        unsafe {
+            let len = self.len();
+            let cap = self.capacity();
+            let ptr = self.storage.as_mut_ptr();
+            (
+                core::slice::from_raw_parts_mut(ptr, len),
+                SliceVec::new(core::slice::from_raw_parts_mut(
+                    ptr.add(len).cast(),
+                    cap.wrapping_sub(len),
+                )),
+            )
+        }
+    }
+
+    /// Calls `f` with the remaining spare capacity of the vector as a
+    /// [`SliceVec<'_, T>`](SliceVec), then adds all the elements that
+    /// were pushed into the [`SliceVec<'_, T>`](SliceVec) to the vector.
+    ///
+    /// This is a safe alternative to [`GenericVec::spare_capacity_mut`] and [`save_spare`].
+    /// The elements are added to the vector even if `f` panics.
+    ///
+    /// ```
+    /// let mut vec = generic_vec::TypeVec::<i32, [i32; 16]>::new();
+    /// vec.push(0);
+    ///
+    /// let len = vec.with_spare_capacity(|spare| {
+    ///     spare.push(1);
+    ///     spare.push(2);
+    ///     spare.len()
+    /// });
+    ///
+    /// assert_eq!(len, 2);
+    /// assert_eq!(vec, [0, 1, 2]);
+    /// ```
+    pub fn with_spare_capacity<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut SliceVec<'_, T>) -> R,
+    {
+        struct Commit<'a, T> {
+            len: &'a mut usize,
+            spare: core::mem::ManuallyDrop<SliceVec<'a, T>>,
+            ptr: *const T,
+            capacity: usize,
+        }
+
+        impl<T> Drop for Commit<'_, T> {
+            fn drop(&mut self) {
+                // `f` could have swapped out the `SliceVec` with another one,
+                // so only add the elements if it's still the spare capacity
+                if self.spare.as_ptr() == self.ptr && self.spare.capacity() == self.capacity {
+                    *self.len += self.spare.len();
+                } else {
+                    unsafe { core::mem::ManuallyDrop::drop(&mut self.spare) }
+                }
+            }
+        }
+
+        // Safety
+        //
+        // The elements from `len..capacity` are guaranteed to be contain
+        // `A::BufferItem`s, as per `Storage`'s safety requirements
+        let spare = unsafe {
             let len = self.len();
             let cap = self.capacity();
             SliceVec::new(core::slice::from_raw_parts_mut(
                 self.storage.as_mut_ptr().add(len).cast(),
                 cap.wrapping_sub(len),
             ))
-        }
+        };
+
+        let mut commit = Commit {
+            ptr: spare.as_ptr(),
+            capacity: spare.capacity(),
+            spare: core::mem::ManuallyDrop::new(spare),
+            len: &mut self.len,
+        };
+
+        let result = f(&mut commit.spare);
+        drop(commit);
+
+        // not checked by `Commit`, because a panic while unwinding would abort
+        #[cfg(feature = "debug-checks")]
+        self.storage.__verify__();
+
+        result
     }
 
     /// Reserve enough space for at least `additional` elements
@@ -818,8 +1447,8 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
             unsafe {
                 self.set_len_unchecked(len);
                 let ptr = self.as_mut_ptr().add(len);
-                let len = diff;
-                core::ptr::drop_in_place(core::slice::from_raw_parts_mut(ptr, len));
+                core::ptr::drop_in_place(core::slice::from_raw_parts_mut(ptr, diff));
+                self.storage.__wipe__(len, diff);
             }
         }
     }
@@ -834,7 +1463,7 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
     ///
     /// May panic or reallocate if the collection is full
     ///
-    /// # Panic behavor
+    /// # Panic behavior
     ///
     /// If `T::clone` panics, then all added items will be dropped. This is different
     /// from `std`, where on panic, items will stay in the `Vec`. This behavior
@@ -850,6 +1479,41 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
         unsafe { extension::Extension::grow(self, additional, value) }
     }
 
+    /// Grows the `GenericVec` in-place by additional elements, by copying `value`
+    ///
+    /// This is the same as [`GenericVec::grow`], but it doesn't need to call `T::clone`.
+    /// For `u8` and `i8`, [`GenericVec::grow_bytes`] is guaranteed to be a `memset`.
+    ///
+    /// ```rust
+    /// # use generic_vec::{gvec, TypeVec};
+    /// let mut vec: TypeVec<u8, [u8; 8]> = gvec![1, 2];
+    /// vec.grow_copy(3, 0xff);
+    /// assert_eq!(vec, [1, 2, 0xff, 0xff, 0xff]);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// May panic or reallocate if the collection is full
+    pub fn grow_copy(&mut self, additional: usize, value: T)
+    where
+        T: Copy,
+    {
+        self.reserve(additional);
+
+        // Safety
+        //
+        // * we reserved enough space for `additional` elements
+        // * `T: Copy`, so there is nothing to drop if we overwrite the spare capacity
+        unsafe {
+            let len = self.len();
+            let spare =
+                core::slice::from_raw_parts_mut(self.as_mut_ptr().add(len).cast::<MaybeUninit<T>>(), additional);
+            // `fill` is a `memset` for single byte types
+            spare.fill(MaybeUninit::new(value));
+            self.set_len_unchecked(len.wrapping_add(additional));
+        }
+    }
+
     /// Grows the `GenericVec` in-place by additional elements.
     ///
     /// This method uses a closure to create new values on every push.
@@ -861,7 +1525,7 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
     ///
     /// May panic or reallocate if the collection is full
     ///
-    /// # Panic behavor
+    /// # Panic behavior
     ///
     /// If `F` panics, then all added items will be dropped. This is different
     /// from `std`, where on panic, items will stay in the `Vec`. This behavior
@@ -891,6 +1555,162 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
         }
     }
 
+    /// Repeats the elements of the `GenericVec` `n` times in-place
+    ///
+    /// Afterwards, the `GenericVec` holds `n` copies of its elements back to back. If `n` is zero,
+    /// the `GenericVec` is cleared, and if the `GenericVec` is empty, this does nothing.
+    /// For `Copy` types, this copies the elements in a few large chunks on `nightly`,
+    /// use [`GenericVec::repeat_copy`] to do the same on stable.
+    ///
+    /// ```rust
+    /// # use generic_vec::{gvec, ArrayVec};
+    /// let mut vec: ArrayVec<i32, 8> = gvec![1, 2];
+    /// vec.repeat(3);
+    /// assert_eq!(vec, [1, 2, 1, 2, 1, 2]);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// May panic or reallocate if the collection can't hold `n` copies of the elements
+    ///
+    /// # Panic behavior
+    ///
+    /// If `T::clone` panics, then all added items will be dropped, and the
+    /// original elements will stay in the `GenericVec`
+    pub fn repeat(&mut self, n: usize)
+    where
+        T: Clone,
+    {
+        if self.is_empty() {
+            return
+        }
+
+        if n == 0 {
+            return self.clear()
+        }
+
+        self.reserve(self.len().checked_mul(n - 1).expect("Capacity overflow"));
+
+        // Safety
+        //
+        // we reserved enough space for `n - 1` more copies of the elements
+        unsafe { extension::Extension::repeat(self, n) }
+    }
+
+    /// Tries to repeat the elements of the `GenericVec` `n` times in-place,
+    /// returns `false` if the collection can't hold `n` copies of the elements
+    ///
+    /// If `false` is returned, the `GenericVec` is left unchanged
+    ///
+    /// ```rust
+    /// # use generic_vec::{gvec, ArrayVec};
+    /// let mut vec: ArrayVec<i32, 4> = gvec![1, 2];
+    /// assert!(!vec.try_repeat(3));
+    /// assert!(vec.try_repeat(2));
+    /// assert_eq!(vec, [1, 2, 1, 2]);
+    /// ```
+    ///
+    /// # Panic behavior
+    ///
+    /// If `T::clone` panics, then all added items will be dropped, and the
+    /// original elements will stay in the `GenericVec`
+    pub fn try_repeat(&mut self, n: usize) -> bool
+    where
+        T: Clone,
+    {
+        if self.is_empty() {
+            return true
+        }
+
+        if n == 0 {
+            self.clear();
+            return true
+        }
+
+        match self.len().checked_mul(n - 1) {
+            Some(additional) if self.try_reserve(additional) => {
+                // Safety
+                //
+                // we reserved enough space for `n - 1` more copies of the elements
+                unsafe { extension::Extension::repeat(self, n) }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Repeats the elements of the `GenericVec` `n` times in-place, by copying them
+    ///
+    /// This is the same as [`GenericVec::repeat`], but it doesn't need to call `T::clone`,
+    /// so the elements are copied in a few large chunks.
+    ///
+    /// ```rust
+    /// # use generic_vec::{gvec, ArrayVec};
+    /// let mut vec: ArrayVec<i32, 8> = gvec![1, 2];
+    /// vec.repeat_copy(3);
+    /// assert_eq!(vec, [1, 2, 1, 2, 1, 2]);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// May panic or reallocate if the collection can't hold `n` copies of the elements
+    pub fn repeat_copy(&mut self, n: usize)
+    where
+        T: Copy,
+    {
+        if self.is_empty() {
+            return
+        }
+
+        if n == 0 {
+            return self.clear()
+        }
+
+        self.reserve(self.len().checked_mul(n - 1).expect("Capacity overflow"));
+
+        // Safety
+        //
+        // we reserved enough space for `n - 1` more copies of the elements
+        unsafe { extension::copy_repeat(self, n) }
+    }
+
+    /// Tries to repeat the elements of the `GenericVec` `n` times in-place, by copying them,
+    /// returns `false` if the collection can't hold `n` copies of the elements
+    ///
+    /// If `false` is returned, the `GenericVec` is left unchanged
+    ///
+    /// ```rust
+    /// # use generic_vec::{gvec, ArrayVec};
+    /// let mut vec: ArrayVec<i32, 4> = gvec![1, 2];
+    /// assert!(!vec.try_repeat_copy(3));
+    /// assert!(vec.try_repeat_copy(2));
+    /// assert_eq!(vec, [1, 2, 1, 2]);
+    /// ```
+    pub fn try_repeat_copy(&mut self, n: usize) -> bool
+    where
+        T: Copy,
+    {
+        if self.is_empty() {
+            return true
+        }
+
+        if n == 0 {
+            self.clear();
+            return true
+        }
+
+        match self.len().checked_mul(n - 1) {
+            Some(additional) if self.try_reserve(additional) => {
+                // Safety
+                //
+                // we reserved enough space for `n - 1` more copies of the elements
+                unsafe { extension::copy_repeat(self, n) }
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Resizes the [`GenericVec`] in-place so that `len` is equal to `new_len`.
     ///
     /// If `new_len` is greater than `len`, the [`GenericVec`] is extended by the difference,
@@ -909,7 +1729,7 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
     ///
     /// May panic or reallocate if the collection is full
     ///
-    /// # Panic behavor
+    /// # Panic behavior
     ///
     /// If `F` panics, then all added items will be dropped. This is different
     /// from `std`, where on panic, items will stay in the `Vec`. This behavior
@@ -925,6 +1745,26 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
         }
     }
 
+    /// Resizes the [`GenericVec`] in-place so that `len` is equal to `new_len`,
+    /// by copying `value`
+    ///
+    /// This is the same as [`GenericVec::resize`], but it doesn't need to call `T::clone`.
+    /// For single byte types, like `u8` and `i8`, this is a `memset`.
+    ///
+    /// # Panic
+    ///
+    /// May panic or reallocate if the collection is full
+    pub fn resize_copy(&mut self, new_len: usize, value: T)
+    where
+        T: Copy,
+    {
+        match new_len.checked_sub(self.len()) {
+            Some(0) => (),
+            Some(additional) => self.grow_copy(additional, value),
+            None => self.truncate(new_len),
+        }
+    }
+
     /// Resizes the [`GenericVec`] in-place so that len is equal to new_len.
     ///
     /// If `new_len` is greater than `len`, the [`GenericVec`] is extended by the
@@ -947,7 +1787,7 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
     ///
     /// May panic or reallocate if the collection is full
     ///
-    /// # Panic behavor
+    /// # Panic behavior
     ///
     /// If `F` panics, then all added items will be dropped. This is different
     /// from `std`, where on panic, items will stay in the `Vec`. This behavior
@@ -965,6 +1805,35 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
     /// Note that this method has no effect on the allocated capacity of the vector.
     pub fn clear(&mut self) { self.truncate(0); }
 
+    /// Clears the vector, then overwrites the entire capacity with zeroes,
+    /// in a way that won't be optimized out.
+    ///
+    /// Note that this method has no effect on the allocated capacity of the vector,
+    /// and it can't wipe copies left behind by earlier reallocations. Use
+    /// [`raw::Zeroizing`] to wipe those too.
+    ///
+    /// ```rust
+    /// # use generic_vec::gvec;
+    /// let mut vec = gvec![1_u32, 2, 3];
+    /// vec.zeroize();
+    /// assert!(vec.is_empty());
+    /// ```
+    #[cfg(feature = "zeroize")]
+    #[cfg_attr(doc, doc(cfg(feature = "zeroize")))]
+    pub fn zeroize(&mut self)
+    where
+        T: zeroize::DefaultIsZeroes,
+    {
+        self.clear();
+        let len = self.capacity().wrapping_mul(core::mem::size_of::<T>());
+        // Safety
+        //
+        // * the storage is valid for writes of `capacity` elements
+        // * `T: DefaultIsZeroes`, so all zeroes is a valid `T`, which
+        //   keeps storages that must stay initialized sound
+        unsafe { raw::zeroizing::wipe(self.as_mut_ptr().cast(), len) }
+    }
+
     /// Appends an element to the back of a collection.
     ///
     /// # Panic
@@ -986,7 +1855,7 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
     /// # Panic
     ///
     /// May panic or reallocate if the collection has less than N elements remaining
-    #[cfg(any(doc, feature = "nightly"))]
+    #[cfg(any(doc, not(no_const_generics)))]
     pub fn push_array<const N: usize>(&mut self, value: [T; N]) -> &mut [T; N] {
         self.reserve(N);
 
@@ -1032,7 +1901,7 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
     ///
     /// * May panic or reallocate if the collection has less than N elements remaining
     /// * Panics if index > len.
-    #[cfg(any(doc, feature = "nightly"))]
+    #[cfg(any(doc, not(no_const_generics)))]
     pub fn insert_array<const N: usize>(&mut self, index: usize, value: [T; N]) -> &mut [T; N] {
         #[cold]
         #[inline(never)]
@@ -1083,7 +1952,7 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
     /// # Panics
     ///
     /// Panics if the collection contains less than `N` elements in it
-    #[cfg(any(doc, feature = "nightly"))]
+    #[cfg(any(doc, not(no_const_generics)))]
     pub fn pop_array<const N: usize>(&mut self) -> [T; N] {
         #[cold]
         #[inline(never)]
@@ -1130,7 +1999,7 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
     /// # Panics
     ///
     /// Panics if `index` is out of bounds or if `index + N > len()`
-    #[cfg(any(doc, feature = "nightly"))]
+    #[cfg(any(doc, not(no_const_generics)))]
     pub fn remove_array<const N: usize>(&mut self, index: usize) -> [T; N] {
         #[cold]
         #[inline(never)]
@@ -1199,7 +2068,7 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
     /// to hold `N` elements.
     ///
     /// Guaranteed to not panic/abort/allocate
-    #[cfg(any(doc, feature = "nightly"))]
+    #[cfg(any(doc, not(no_const_generics)))]
     pub fn try_push_array<const N: usize>(&mut self, value: [T; N]) -> Result<&mut [T; N], [T; N]> {
         if self.remaining_capacity() < N {
             Err(value)
@@ -1234,7 +2103,7 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
     /// to hold `N` elements or index is out of bounds
     ///
     /// Guaranteed to not panic/abort/allocate
-    #[cfg(any(doc, feature = "nightly"))]
+    #[cfg(any(doc, not(no_const_generics)))]
     pub fn try_insert_array<const N: usize>(&mut self, index: usize, value: [T; N]) -> Result<&mut [T; N], [T; N]> {
         if self.capacity().wrapping_sub(self.len()) < N || index > self.len() {
             Err(value)
@@ -1266,7 +2135,7 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
     /// Returns `None` if the collection is has less than N elements
     ///
     /// Guaranteed to not panic/abort/allocate
-    #[cfg(any(doc, feature = "nightly"))]
+    #[cfg(any(doc, not(no_const_generics)))]
     pub fn try_pop_array<const N: usize>(&mut self) -> Option<[T; N]> {
         if self.is_empty() {
             None
@@ -1300,7 +2169,7 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
     /// or `index` is out of bounds.
     ///
     /// Guaranteed to not panic/abort/allocate
-    #[cfg(any(doc, feature = "nightly"))]
+    #[cfg(any(doc, not(no_const_generics)))]
     pub fn try_remove_array<const N: usize>(&mut self, index: usize) -> Option<[T; N]> {
         if self.len() < index || self.len().wrapping_sub(index) < N {
             None
@@ -1333,6 +2202,15 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
         }
     }
 
+    #[doc(hidden)]
+    #[cfg(not(no_const_generics))]
+    pub unsafe fn __push_array_const_checked<const N: usize>(&mut self, value: [T; N]) -> &mut [T; N] {
+        #[allow(clippy::let_unit_value)]
+        let () = ArrayFits::<T, S, N>::CHECK;
+
+        unsafe { self.push_array_unchecked(value) }
+    }
+
     /// Appends an element to the back of a collection.
     ///
     /// # Safety
@@ -1343,9 +2221,8 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
             panic!("Tried to push an element into a zero-capacity vector!")
         }
 
-        debug_assert_ne!(
-            self.len(),
-            self.capacity(),
+        debug_check!(
+            self.len() < self.capacity(),
             "Tried to `push_unchecked` past capacity! This is UB in release mode"
         );
 
@@ -1366,7 +2243,7 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
     /// # Safety
     ///
     /// the collection's remaining capacity must be at least N
-    #[cfg(any(doc, feature = "nightly"))]
+    #[cfg(any(doc, not(no_const_generics)))]
     pub unsafe fn push_array_unchecked<const N: usize>(&mut self, value: [T; N]) -> &mut [T; N] {
         match S::CONST_CAPACITY {
             Some(n) if n < N => {
@@ -1375,6 +2252,13 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
             _ => (),
         }
 
+        debug_check!(
+            self.remaining_capacity() >= N,
+            "Tried to push {} elements into a vector with {} remaining capacity! This is UB in release mode",
+            N,
+            self.remaining_capacity(),
+        );
+
         // Safety
         //
         // the collection has at least N remaining elements of capacity left,
@@ -1402,11 +2286,22 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
                 panic!("Tried to insert an element into a zero-capacity vector!")
             }
 
+            let len = self.len();
+            debug_check!(
+                index <= len,
+                "Tried to insert an element at index {} into a {} length vector! This is UB in release mode",
+                index,
+                len,
+            );
+            debug_check!(
+                len < self.capacity(),
+                "Tried to `insert_unchecked` past capacity! This is UB in release mode"
+            );
+
             // Safety
             //
             // * the index is in bounds
             // * the collection is't full so `ptr.add(len)` is valid to write 1 element
-            let len = self.len();
             self.set_len_unchecked(len.wrapping_add(1));
             let ptr = self.storage.as_mut_ptr().add(index);
             ptr.add(1).copy_from(ptr, len.wrapping_sub(index));
@@ -1422,7 +2317,7 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
     ///
     /// * the collection's remaining capacity must be at least N
     /// * hte index must be in bounds
-    #[cfg(any(doc, feature = "nightly"))]
+    #[cfg(any(doc, not(no_const_generics)))]
     pub unsafe fn insert_array_unchecked<const N: usize>(&mut self, index: usize, value: [T; N]) -> &mut [T; N] {
         match S::CONST_CAPACITY {
             Some(n) if n < N => {
@@ -1431,6 +2326,19 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
             _ => (),
         }
 
+        debug_check!(
+            index <= self.len(),
+            "Tried to insert elements at index {} into a {} length vector! This is UB in release mode",
+            index,
+            self.len(),
+        );
+        debug_check!(
+            self.remaining_capacity() >= N,
+            "Tried to insert {} elements into a vector with {} remaining capacity! This is UB in release mode",
+            N,
+            self.remaining_capacity(),
+        );
+
         // Safety
         //
         // * the index is in bounds
@@ -1461,8 +2369,8 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
         }
 
         let len = self.len();
-        debug_assert_ne!(
-            len, 0,
+        debug_check!(
+            len != 0,
             "Tried to `pop_unchecked` an empty array vec! This is UB in release mode"
         );
 
@@ -1472,7 +2380,9 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
         unsafe {
             let len = len.wrapping_sub(1);
             self.set_len_unchecked(len);
-            self.as_mut_ptr().add(len).read()
+            let value = self.as_mut_ptr().add(len).read();
+            self.storage.__wipe__(len, 1);
+            value
         }
     }
 
@@ -1481,7 +2391,7 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
     /// # Safety
     ///
     /// The collection must contain at least `N` elements in it
-    #[cfg(any(doc, feature = "nightly"))]
+    #[cfg(any(doc, not(no_const_generics)))]
     pub unsafe fn pop_array_unchecked<const N: usize>(&mut self) -> [T; N] {
         match S::CONST_CAPACITY {
             Some(n) if n < N => panic!("Tried to remove {} elements from a {} capacity vector!", N, n),
@@ -1489,19 +2399,22 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
         }
 
         let len = self.len();
-        debug_assert!(
-            len > N,
+        debug_check!(
+            len >= N,
             "Tried to remove {} elements from a {} length vector! This is UB in release mode",
             N,
             len,
         );
+
         // Safety
         //
         // * the collection has at least `N` elements, so `ptr.add(len - N)` is valid to read `N` elements
         unsafe {
             let len = len.wrapping_sub(N);
             self.set_len_unchecked(len);
-            self.as_mut_ptr().add(len).cast::<[T; N]>().read()
+            let value = self.as_mut_ptr().add(len).cast::<[T; N]>().read();
+            self.storage.__wipe__(len, N);
+            value
         }
     }
 
@@ -1519,8 +2432,8 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
 
         let len = self.len();
 
-        debug_assert!(
-            index <= len,
+        debug_check!(
+            index < len,
             "Tried to remove an element at index {} from a {} length vector! This is UB in release mode",
             index,
             len,
@@ -1535,6 +2448,7 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
             let ptr = self.storage.as_mut_ptr().add(index);
             let value = ptr.read();
             ptr.copy_from(ptr.add(1), len.wrapping_sub(index).wrapping_sub(1));
+            self.storage.__wipe__(len.wrapping_sub(1), 1);
             value
         }
     }
@@ -1546,7 +2460,7 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
     ///
     /// the collection must contain at least N elements, and
     /// index must be in bounds
-    #[cfg(any(doc, feature = "nightly"))]
+    #[cfg(any(doc, not(no_const_generics)))]
     pub unsafe fn remove_array_unchecked<const N: usize>(&mut self, index: usize) -> [T; N] {
         match S::CONST_CAPACITY {
             Some(n) if n < N => panic!("Tried to remove {} elements from a {} capacity vector!", N, n),
@@ -1554,14 +2468,14 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
         }
 
         let len = self.len();
-        debug_assert!(
+        debug_check!(
             index <= len,
             "Tried to remove elements at index {} from a {} length vector! This is UB in release mode",
             index,
             len,
         );
-        debug_assert!(
-            len.wrapping_sub(index) > N,
+        debug_check!(
+            len - index >= N,
             "Tried to remove {} elements from a {} length vector! This is UB in release mode",
             N,
             len,
@@ -1578,6 +2492,7 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
             if N != 0 {
                 ptr.copy_from(ptr.add(N), len.wrapping_sub(index).wrapping_sub(N));
             }
+            self.storage.__wipe__(len.wrapping_sub(N), N);
             value
         }
     }
@@ -1596,6 +2511,13 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
             panic!("Tried to remove an element from a zero-capacity vector!")
         }
 
+        debug_check!(
+            index < self.len(),
+            "Tried to remove an element at index {} from a {} length vector! This is UB in release mode",
+            index,
+            self.len(),
+        );
+
         // Safety
         //
         // * the index is in bounds
@@ -1608,6 +2530,7 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
             let end = ptr.add(len.wrapping_sub(1));
             let value = at.read();
             at.copy_from(end, 1);
+            self.storage.__wipe__(len.wrapping_sub(1), 1);
             value
         }
     }
@@ -1687,9 +2610,11 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
             // * other has reserved enough space
             // * we ignore all elements after index
             let slice = self.get_unchecked(index..);
-            other.reserve(slice.len());
+            let len = slice.len();
+            other.reserve(len);
             other.extend_from_slice_unchecked(slice);
             self.set_len_unchecked(index);
+            self.storage.__wipe__(index, len);
         }
     }
 
@@ -1723,6 +2648,145 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
         self.split_off(0)
     }
 
+    /// Drops all the elements in `self`, and reuses the backing storage
+    /// for an empty vector of a different element type
+    ///
+    /// The capacity of the new vector is in units of `U`, so it may be
+    /// larger or smaller than the current capacity.
+    ///
+    /// ```rust
+    /// # use generic_vec::HeapVec;
+    /// let mut scratch: HeapVec<&'static str> = HeapVec::with_capacity(16);
+    ///
+    /// for i in 0..3 {
+    ///     let line = format!("{} {}", i, i + 1);
+    ///
+    ///     // borrow from `line`, which doesn't live as long as `scratch`
+    ///     let mut words = scratch.recycle::<&str>();
+    ///     words.extend(line.split(' '));
+    ///     assert_eq!(words.len(), 2);
+    ///
+    ///     scratch = words.recycle();
+    /// }
+    ///
+    /// assert!(scratch.is_empty());
+    /// assert!(scratch.capacity() >= 16);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// If the storage cannot hold type `U`
+    pub fn recycle<U>(mut self) -> GenericVec<U, S>
+    where
+        S: Sized + Storage<U>,
+    {
+        assert!(<S as Storage<U>>::IS_ALIGNED, "The storage must be aligned to `U`");
+
+        self.clear();
+        let (_, storage) = self.into_raw_parts();
+
+        // Safety
+        //
+        // the length is zero, so there are no elements to initialize
+        unsafe { GenericVec::from_raw_parts(0, storage) }
+    }
+
+    /// Transforms each element of `self` with `f`, reusing the backing storage for
+    /// the new elements
+    ///
+    /// If `f` panics, all the elements that were already mapped, and all the elements
+    /// that haven't been mapped yet, will be dropped.
+    ///
+    /// ```rust
+    /// # use generic_vec::HeapVec;
+    /// let vec: HeapVec<u32> = (1..5).collect();
+    /// let ptr = vec.as_ptr() as usize;
+    ///
+    /// let vec = vec.map_in_place(|x| x as u16 * 10);
+    ///
+    /// assert_eq!(vec, [10, 20, 30, 40]);
+    /// assert_eq!(vec.as_ptr() as usize, ptr);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// If `U` is larger than `T`, or if the storage cannot hold type `U`
+    pub fn map_in_place<U, F>(mut self, mut f: F) -> GenericVec<U, S>
+    where
+        S: Sized + Storage<U>,
+        F: FnMut(T) -> U,
+    {
+        struct MapInPlace<T, U> {
+            src: *mut T,
+            dst: *mut U,
+            len: usize,
+            read: usize,
+            written: usize,
+        }
+
+        impl<T, U> Drop for MapInPlace<T, U> {
+            fn drop(&mut self) {
+                // Safety
+                //
+                // * the first `written` elements have been mapped to `U`
+                // * the elements after `read` haven't been moved out yet
+                unsafe {
+                    ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.dst, self.written));
+                    ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                        self.src.add(self.read),
+                        self.len - self.read,
+                    ));
+                }
+            }
+        }
+
+        assert!(
+            core::mem::size_of::<U>() <= core::mem::size_of::<T>(),
+            "Tried to map in place to a larger type"
+        );
+        assert!(<S as Storage<U>>::IS_ALIGNED, "The storage must be aligned to `U`");
+
+        let len = self.len();
+        assert!(len <= Storage::<U>::capacity(&self.storage));
+
+        // Safety
+        //
+        // The guard takes ownership of all the elements
+        unsafe { self.set_len_unchecked(0) }
+
+        let mut guard = MapInPlace {
+            src: self.as_mut_ptr(),
+            dst: Storage::<U>::as_mut_ptr(&mut self.storage),
+            len,
+            read: 0,
+            written: 0,
+        };
+
+        while guard.read < len {
+            // Safety
+            //
+            // * `U` is not larger than `T`, so the `U` at `written` can only overlap
+            //   with `T`s that have already been read
+            // * `IS_ALIGNED` guarantees that the `U` is well aligned
+            unsafe {
+                let value = guard.src.add(guard.read).read();
+                guard.read += 1;
+                guard.dst.add(guard.written).write(f(value));
+                guard.written += 1;
+            }
+        }
+
+        core::mem::forget(guard);
+
+        let (_, storage) = self.into_raw_parts();
+
+        // Safety
+        //
+        // all `len` elements were mapped to `U`, and `len` fits in
+        // the storage's capacity for `U`
+        unsafe { GenericVec::from_raw_parts(len, storage) }
+    }
+
     /// Creates a raw cursor that can be used to remove elements in the specified range.
     /// Usage of [`RawCursor`](iter::RawCursor) is `unsafe` because it doesn't do any checks.
     /// [`RawCursor`](iter::RawCursor) is meant to be a low level tool to implement fancier
@@ -1776,6 +2840,36 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
         iter::Drain::new(self.raw_cursor(range))
     }
 
+    /// Creates a parallel draining iterator that removes the specified range in the
+    /// vector and yields the removed items.
+    ///
+    /// When the iterator is dropped, all elements in the range are removed from
+    /// the vector, even if the iterator was not fully consumed.
+    ///
+    /// ```rust
+    /// use generic_vec::{HeapVec, gvec};
+    /// use rayon::prelude::*;
+    ///
+    /// let mut vec: HeapVec<i32> = gvec![1, 2, 3, 4, 5];
+    /// let sum: i32 = vec.par_drain(1..4).sum();
+    /// assert_eq!(sum, 9);
+    /// assert_eq!(vec, [1, 5]);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the starting point is greater than the end point or if the end point
+    /// is greater than the length of the vector.
+    #[inline]
+    #[cfg(feature = "rayon")]
+    #[cfg_attr(doc, doc(cfg(feature = "rayon")))]
+    pub fn par_drain<R>(&mut self, range: R) -> iter::ParDrain<'_, T, S>
+    where
+        R: RangeBounds<usize>,
+    {
+        iter::ParDrain::new(self.raw_cursor(range))
+    }
+
     /// Creates an iterator which uses a closure to determine if an element should be removed.
     ///
     /// If the closure returns true, then the element is removed and yielded.
@@ -1807,6 +2901,11 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
     /// The input iterator replace_with is only consumed when the [`Splice`](iter::Splice)
     /// value is dropped
     ///
+    /// The replacement items are written directly into the vector, this never allocates
+    /// outside of the vector's storage. The vector will only make space for as many
+    /// items as `replace_with`'s size hint allows, so iterators with an exact size hint
+    /// will move the tail of the vector at most once.
+    ///
     /// # Panic
     ///
     /// Panics if the starting point is greater than the end point or if the end point
@@ -1841,7 +2940,7 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
     /// * You must not drop any of the elements in `slice`
     /// * There must be at least `slice.len()` remaining capacity in the vector
     pub unsafe fn extend_from_slice_unchecked(&mut self, slice: &[T]) {
-        debug_assert!(
+        debug_check!(
             self.remaining_capacity() >= slice.len(),
             "Not enough capacity to hold the slice"
         );
@@ -1864,7 +2963,7 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
     /// to work with slices instead. If and when Rust gets specialization this
     /// function will likely be deprecated (but still available).
     ///
-    /// # Panic behavor
+    /// # Panic behavior
     ///
     /// If `T::clone` panics, then all newly added items will be dropped. This is different
     /// from `std`, where on panic, newly added items will stay in the `Vec`. This behavior
@@ -1881,6 +2980,34 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
         unsafe { extension::Extension::extend_from_slice(self, slice) }
     }
 
+    /// Copies and appends all elements in a slice to the `GenericVec`.
+    ///
+    /// This is the same as [`GenericVec::extend_from_slice`], but it doesn't
+    /// need to call `T::clone`, so it's a single `memcpy`.
+    ///
+    /// ```rust
+    /// # use generic_vec::{gvec, TypeVec};
+    /// let mut vec: TypeVec<i32, [i32; 8]> = gvec![1, 2];
+    /// vec.extend_from_copy_slice(&[3, 4, 5]);
+    /// assert_eq!(vec, [1, 2, 3, 4, 5]);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// May try to panic/reallocate if there is not enough capacity for the slice
+    pub fn extend_from_copy_slice(&mut self, slice: &[T])
+    where
+        T: Copy,
+    {
+        self.reserve(slice.len());
+
+        // Safety
+        //
+        // * we reserved enough space
+        // * `T: Copy`, so there is nothing to drop
+        unsafe { self.extend_from_slice_unchecked(slice) }
+    }
+
     /// Replaces all of the current elements with the ones in the slice
     ///
     /// equivalent to the following
@@ -1917,6 +3044,22 @@ impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
         self.extend_from_slice(tail);
     }
 
+    /// Replaces all of the current elements with copies of the ones in the slice
+    ///
+    /// This is the same as [`GenericVec::clone_from`], but it doesn't need to call
+    /// `T::clone`, so it's a single `memcpy`.
+    ///
+    /// # Panic
+    ///
+    /// May try to panic/reallocate if there is not enough capacity for the slice
+    pub fn clone_from_copy_slice(&mut self, source: &[T])
+    where
+        T: Copy,
+    {
+        self.clear();
+        self.extend_from_copy_slice(source);
+    }
+
     /// Removes all but the first of consecutive elements in the vector satisfying
     /// a given equality relation.
     ///
@@ -3,20 +3,73 @@
 #[cfg(feature = "alloc")]
 use std::boxed::Box;
 
+mod arena;
 mod array;
 #[cfg(any(doc, feature = "alloc"))]
+pub(crate) mod boxed;
+mod counting;
+mod dyn_storage;
+#[cfg(any(doc, feature = "alloc"))]
 mod heap;
+mod limited;
+#[cfg(not(no_const_generics))]
+mod pool;
 mod slice;
 mod uninit;
+#[cfg(any(doc, feature = "alloc"))]
+mod vec;
 mod zero_sized;
 
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+mod anon_mmap;
 mod capacity;
+#[cfg(feature = "debug-checks")]
+mod checked;
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+mod mmap;
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+mod reserved;
+#[cfg(feature = "zeroize")]
+pub(crate) mod zeroizing;
 
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+#[cfg_attr(doc, doc(cfg(all(feature = "mmap", target_os = "linux"))))]
+pub use anon_mmap::AnonMmap;
+#[cfg(any(doc, feature = "alloc"))]
+#[cfg_attr(doc, doc(cfg(feature = "alloc")))]
+pub use boxed::BoxedSlice;
+#[cfg(feature = "debug-checks")]
+#[cfg_attr(doc, doc(cfg(feature = "debug-checks")))]
+pub use checked::Checked;
+#[cfg(any(doc, all(feature = "alloc", not(no_const_generics))))]
+#[cfg_attr(doc, doc(cfg(feature = "alloc")))]
+pub use heap::AlignedHeap;
 #[cfg(any(doc, feature = "alloc"))]
 pub use heap::Heap;
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+#[cfg_attr(doc, doc(cfg(all(feature = "mmap", target_os = "linux"))))]
+pub use mmap::MmapStorage;
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+#[cfg_attr(doc, doc(cfg(all(feature = "mmap", target_os = "linux"))))]
+pub use reserved::{Appender, Reserved};
+#[cfg(feature = "zeroize")]
+#[cfg_attr(doc, doc(cfg(feature = "zeroize")))]
+pub use zeroizing::Zeroizing;
 
+pub use arena::{Arena, ArenaBuffer};
+pub use counting::{Counting, CountingEvent, CountingStats};
+pub use dyn_storage::DynStorage;
+pub use limited::Limited;
+#[cfg(target_has_atomic = "ptr")]
+#[cfg_attr(doc, doc(cfg(target_has_atomic = "ptr")))]
+pub use limited::{Budget, SharedLimited};
+#[cfg(not(no_const_generics))]
+pub use pool::{Pool, PoolBlock, PoolSlot};
 pub use slice::UninitSlice;
 pub use uninit::UninitBuffer;
+#[cfg(any(doc, feature = "alloc"))]
+#[cfg_attr(doc, doc(cfg(feature = "alloc")))]
+pub use vec::VecStorage;
 pub use zero_sized::ZeroSized;
 
 /// A [`Storage`] that can only contain initialized `Storage::Item`
@@ -25,6 +78,7 @@ pub unsafe trait StorageInit<T>: Storage<T> {}
 /// A type that can hold `T`s, and potentially
 /// reserve space for more `Self::Items`s
 pub unsafe trait Storage<T> {
+    /// Must be `Some(CAPACITY)` if this storage implements [`FixedCapacity`]
     #[doc(hidden)]
     const CONST_CAPACITY: Option<usize> = None;
 
@@ -69,6 +123,57 @@ pub unsafe trait Storage<T> {
     ///
     /// If `Ok(())` is returned, the `capacity` must be at least `new_capacity`
     fn try_reserve(&mut self, new_capacity: usize) -> bool;
+
+    /// Like [`Storage::reserve`], but doesn't round the capacity up past `new_capacity`
+    /// if the storage can avoid it, so adapters that cap the capacity can keep the
+    /// storage within the cap.
+    #[doc(hidden)]
+    #[inline(always)]
+    #[allow(non_snake_case)]
+    fn __reserve_exact__(&mut self, new_capacity: usize) { self.reserve(new_capacity) }
+
+    /// Like [`Storage::try_reserve`], but doesn't round the capacity up past `new_capacity`
+    /// if the storage can avoid it, see `__reserve_exact__`
+    #[doc(hidden)]
+    #[inline(always)]
+    #[allow(non_snake_case)]
+    fn __try_reserve_exact__(&mut self, new_capacity: usize) -> bool { self.try_reserve(new_capacity) }
+
+    /// Called after the `len` elements starting at `index` were removed from
+    /// the storage, so storages that need to wipe freed memory can do so.
+    ///
+    /// # Safety
+    ///
+    /// The range `index..index + len` must be in bounds of the storage's
+    /// capacity, and must not contain any live elements
+    #[doc(hidden)]
+    #[inline(always)]
+    #[allow(non_snake_case)]
+    unsafe fn __wipe__(&mut self, _index: usize, _len: usize) {}
+
+    /// Called whenever the length of a vector changes, so storages that check
+    /// for memory corruption can do so.
+    ///
+    /// This is only called if the `debug-checks` feature is enabled
+    #[doc(hidden)]
+    #[inline(always)]
+    #[allow(non_snake_case)]
+    fn __verify__(&self) {}
+
+    /// Called when a vector is dropped, or gives up its storage, with the length
+    /// it had, before the elements are dropped, so storages that persist the length can do so.
+    ///
+    /// Returns true if the storage took ownership of the elements, in which
+    /// case the vector must not drop them.
+    ///
+    /// # Safety
+    ///
+    /// The first `len` elements of the storage must be initialized, and this
+    /// must only be called once, right before the vector is dropped or gives up its storage
+    #[doc(hidden)]
+    #[inline(always)]
+    #[allow(non_snake_case)]
+    unsafe fn __on_drop__(&mut self, _len: usize) -> bool { false }
 }
 
 /// A storage that can be initially created with a given capacity
@@ -89,6 +194,26 @@ pub unsafe trait StorageWithCapacity<T>: Storage<T> + Default {
     }
 }
 
+/// A storage whose capacity is known at compile time
+///
+/// This allows some operations to check at compile time that the elements fit,
+/// instead of panicking at run time.
+///
+/// ```rust
+/// use generic_vec::raw::{FixedCapacity, UninitBuffer};
+///
+/// assert_eq!(<[i32; 4] as FixedCapacity<i32>>::CAPACITY, 4);
+/// assert_eq!(<UninitBuffer<[u32; 2]> as FixedCapacity<u16>>::CAPACITY, 4);
+/// ```
+///
+/// # Safety
+///
+/// `Storage::capacity` must always return `CAPACITY`
+pub unsafe trait FixedCapacity<T>: Storage<T> {
+    /// The capacity of every storage of this type
+    const CAPACITY: usize;
+}
+
 unsafe impl<T, S: ?Sized + StorageInit<T>> StorageInit<T> for &mut S {}
 unsafe impl<T, S: ?Sized + Storage<T>> Storage<T> for &mut S {
     #[doc(hidden)]
@@ -104,6 +229,30 @@ unsafe impl<T, S: ?Sized + Storage<T>> Storage<T> for &mut S {
     fn reserve(&mut self, new_capacity: usize) { S::reserve(self, new_capacity) }
     #[inline]
     fn try_reserve(&mut self, new_capacity: usize) -> bool { S::try_reserve(self, new_capacity) }
+    #[doc(hidden)]
+    #[inline(always)]
+    #[allow(non_snake_case)]
+    fn __reserve_exact__(&mut self, new_capacity: usize) { S::__reserve_exact__(self, new_capacity) }
+    #[doc(hidden)]
+    #[inline(always)]
+    #[allow(non_snake_case)]
+    fn __try_reserve_exact__(&mut self, new_capacity: usize) -> bool { S::__try_reserve_exact__(self, new_capacity) }
+    #[doc(hidden)]
+    #[inline(always)]
+    #[allow(non_snake_case)]
+    unsafe fn __wipe__(&mut self, index: usize, len: usize) { unsafe { S::__wipe__(self, index, len) } }
+    #[doc(hidden)]
+    #[inline(always)]
+    #[allow(non_snake_case)]
+    fn __verify__(&self) { S::__verify__(self) }
+    #[doc(hidden)]
+    #[inline(always)]
+    #[allow(non_snake_case)]
+    unsafe fn __on_drop__(&mut self, len: usize) -> bool { unsafe { S::__on_drop__(self, len) } }
+}
+
+unsafe impl<T, S: ?Sized + FixedCapacity<T>> FixedCapacity<T> for &mut S {
+    const CAPACITY: usize = S::CAPACITY;
 }
 
 #[cfg(any(doc, feature = "alloc"))]
@@ -124,6 +273,31 @@ unsafe impl<T, S: ?Sized + Storage<T>> Storage<T> for Box<S> {
     fn reserve(&mut self, new_capacity: usize) { S::reserve(self, new_capacity) }
     #[inline]
     fn try_reserve(&mut self, new_capacity: usize) -> bool { S::try_reserve(self, new_capacity) }
+    #[doc(hidden)]
+    #[inline(always)]
+    #[allow(non_snake_case)]
+    fn __reserve_exact__(&mut self, new_capacity: usize) { S::__reserve_exact__(self, new_capacity) }
+    #[doc(hidden)]
+    #[inline(always)]
+    #[allow(non_snake_case)]
+    fn __try_reserve_exact__(&mut self, new_capacity: usize) -> bool { S::__try_reserve_exact__(self, new_capacity) }
+    #[doc(hidden)]
+    #[inline(always)]
+    #[allow(non_snake_case)]
+    unsafe fn __wipe__(&mut self, index: usize, len: usize) { unsafe { S::__wipe__(self, index, len) } }
+    #[doc(hidden)]
+    #[inline(always)]
+    #[allow(non_snake_case)]
+    fn __verify__(&self) { S::__verify__(self) }
+    #[doc(hidden)]
+    #[inline(always)]
+    #[allow(non_snake_case)]
+    unsafe fn __on_drop__(&mut self, len: usize) -> bool { unsafe { S::__on_drop__(self, len) } }
+}
+
+#[cfg(any(doc, feature = "alloc"))]
+unsafe impl<T, S: ?Sized + FixedCapacity<T>> FixedCapacity<T> for Box<S> {
+    const CAPACITY: usize = S::CAPACITY;
 }
 
 #[cfg(any(doc, feature = "alloc"))]
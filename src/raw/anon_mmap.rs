@@ -0,0 +1,275 @@
+use crate::{
+    raw::{
+        mmap::{round_to_page, MIN_PAGE_SIZE},
+        Storage, StorageWithCapacity,
+    },
+    GenericVec,
+};
+
+use core::{
+    marker::PhantomData,
+    mem::{align_of, size_of},
+    ptr::NonNull,
+};
+use std::io;
+
+const INIT_ALLOC_CAPACITY: usize = 4;
+
+/// A heap-like storage that is backed by an anonymous memory mapping
+///
+/// Growing the storage remaps it with `mremap`, which moves the pages instead of
+/// copying the elements, so resizing very large vectors stays cheap. The capacity is
+/// always a whole number of pages, and memory can be given back to the OS with
+/// [`GenericVec::shrink_to`] and [`GenericVec::release_spare`].
+///
+/// ```rust
+/// use generic_vec::{GenericVec, raw::AnonMmap};
+///
+/// let mut vec = GenericVec::with_storage(AnonMmap::new().with_huge_pages());
+/// vec.extend(0_u64..100_000);
+/// vec.truncate(10);
+///
+/// vec.shrink_to_fit();
+/// assert_eq!(vec, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+/// assert!(vec.capacity() < 100_000);
+/// ```
+///
+/// Usable with the `mmap` feature on Linux
+pub struct AnonMmap<T> {
+    ptr: NonNull<u8>,
+    map_len: usize,
+    huge_pages: bool,
+    mark: PhantomData<T>,
+}
+
+unsafe impl<T: Send> Send for AnonMmap<T> {}
+unsafe impl<T: Sync> Sync for AnonMmap<T> {}
+
+enum OnFailure {
+    Abort,
+    Error,
+}
+
+impl<T> Drop for AnonMmap<T> {
+    fn drop(&mut self) {
+        if self.map_len != 0 {
+            unsafe {
+                libc::munmap(self.ptr.as_ptr().cast(), self.map_len);
+            }
+        }
+    }
+}
+
+impl<T> Default for AnonMmap<T> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<T> AnonMmap<T> {
+    /// Create a new zero-capacity storage, nothing is mapped until the storage is reserved
+    pub const fn new() -> Self {
+        Self {
+            ptr: NonNull::<T>::dangling().cast(),
+            map_len: 0,
+            huge_pages: false,
+            mark: PhantomData,
+        }
+    }
+
+    /// Ask the kernel to back this storage with transparent huge pages
+    ///
+    /// This is only a hint, it's silently ignored if the kernel doesn't support
+    /// transparent huge pages
+    pub fn with_huge_pages(mut self) -> Self {
+        self.huge_pages = true;
+        self.advise_huge_pages();
+        self
+    }
+
+    /// Returns true if this storage asks for transparent huge pages
+    pub fn huge_pages(&self) -> bool { self.huge_pages }
+
+    fn advise_huge_pages(&self) {
+        if self.huge_pages && self.map_len != 0 {
+            // the hint is best effort, so errors are ignored
+            unsafe {
+                libc::madvise(self.ptr.as_ptr().cast(), self.map_len, libc::MADV_HUGEPAGE);
+            }
+        }
+    }
+
+    fn byte_len(capacity: usize) -> Option<usize> { round_to_page(capacity.checked_mul(size_of::<T>())?) }
+
+    fn with_capacity(capacity: usize) -> Self {
+        let mut storage = Self::new();
+
+        if size_of::<T>() != 0 && capacity != 0 {
+            let map_len = Self::byte_len(capacity).expect("capacity overflow");
+
+            if let Err(err) = storage.remap(map_len) {
+                map_error(capacity, err)
+            }
+        }
+
+        storage
+    }
+
+    /// Resize the mapping to `map_len` bytes, moving it if needed
+    fn remap(&mut self, map_len: usize) -> io::Result<()> {
+        let ptr = if self.map_len == 0 {
+            unsafe {
+                libc::mmap(
+                    core::ptr::null_mut(),
+                    map_len,
+                    libc::PROT_READ | libc::PROT_WRITE,
+                    libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+                    -1,
+                    0,
+                )
+            }
+        } else {
+            unsafe { libc::mremap(self.ptr.as_ptr().cast(), self.map_len, map_len, libc::MREMAP_MAYMOVE) }
+        };
+
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error())
+        }
+
+        self.ptr = NonNull::new(ptr.cast()).expect("mmap returned a null pointer");
+        self.map_len = map_len;
+        self.advise_huge_pages();
+
+        Ok(())
+    }
+
+    /// Unmap every page past the first `map_len` bytes
+    fn truncate_map(&mut self, map_len: usize) {
+        if map_len >= self.map_len {
+            return
+        }
+
+        unsafe {
+            libc::munmap(self.ptr.as_ptr().add(map_len).cast(), self.map_len - map_len);
+        }
+
+        if map_len == 0 {
+            self.ptr = NonNull::<T>::dangling().cast();
+        }
+
+        self.map_len = map_len;
+    }
+
+    /// Give the pages past the first `offset` bytes back to the OS, without unmapping them
+    ///
+    /// The pages read as zeros the next time they are touched
+    fn discard_from(&mut self, offset: usize) {
+        let offset = round_to_page(offset).unwrap_or(self.map_len);
+
+        if offset < self.map_len {
+            unsafe {
+                libc::madvise(
+                    self.ptr.as_ptr().add(offset).cast(),
+                    self.map_len - offset,
+                    libc::MADV_DONTNEED,
+                );
+            }
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn reserve_slow(&mut self, new_capacity: usize, on_failure: OnFailure) -> bool {
+        let capacity = Storage::<T>::capacity(self);
+        assert!(new_capacity > capacity);
+
+        // grow by at least doubling
+        let new_capacity = new_capacity.max(capacity.saturating_mul(2)).max(INIT_ALLOC_CAPACITY);
+
+        let result = match Self::byte_len(new_capacity) {
+            Some(map_len) => self.remap(map_len),
+            None => Err(io::Error::new(io::ErrorKind::InvalidInput, "capacity overflow")),
+        };
+
+        match (result, on_failure) {
+            (Ok(()), _) => true,
+            (Err(err), OnFailure::Abort) => map_error(new_capacity, err),
+            (Err(_), OnFailure::Error) => false,
+        }
+    }
+}
+
+#[cold]
+#[inline(never)]
+fn map_error(new_capacity: usize, err: io::Error) -> ! {
+    panic!("Could not map memory for {} elements: {}", new_capacity, err)
+}
+
+unsafe impl<T> Storage<T> for AnonMmap<T> {
+    const IS_ALIGNED: bool = align_of::<T>() <= MIN_PAGE_SIZE;
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        match size_of::<T>() {
+            0 => usize::MAX,
+            size => self.map_len / size,
+        }
+    }
+
+    #[inline]
+    fn as_ptr(&self) -> *const T { self.ptr.as_ptr().cast() }
+
+    #[inline]
+    fn as_mut_ptr(&mut self) -> *mut T { self.ptr.as_ptr().cast() }
+
+    #[inline]
+    fn reserve(&mut self, new_capacity: usize) {
+        if Storage::<T>::capacity(self) < new_capacity {
+            let _ = self.reserve_slow(new_capacity, OnFailure::Abort);
+        }
+    }
+
+    #[inline]
+    fn try_reserve(&mut self, new_capacity: usize) -> bool {
+        Storage::<T>::capacity(self) >= new_capacity || self.reserve_slow(new_capacity, OnFailure::Error)
+    }
+}
+
+unsafe impl<T> StorageWithCapacity<T> for AnonMmap<T> {
+    fn with_capacity(capacity: usize) -> Self { Self::with_capacity(capacity) }
+}
+
+impl<T> GenericVec<T, AnonMmap<T>> {
+    /// Shrink the capacity of the vector as much as possible
+    ///
+    /// The capacity is always rounded up to a whole number of pages
+    pub fn shrink_to_fit(&mut self) { self.shrink_to(0) }
+
+    /// Shrink the capacity of the vector to at least `max(len, min_capacity)`
+    ///
+    /// The pages past the new capacity are unmapped, so the elements are never copied.
+    /// The capacity is always rounded up to a whole number of pages.
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        if size_of::<T>() == 0 {
+            return
+        }
+
+        let capacity = self.len().max(min_capacity);
+
+        // if the capacity overflows, it's larger than the mapping
+        if let Some(map_len) = AnonMmap::<T>::byte_len(capacity) {
+            self.storage.truncate_map(map_len);
+        }
+    }
+
+    /// Give the memory backing the spare capacity back to the OS, without
+    /// changing the capacity
+    ///
+    /// This uses `madvise(MADV_DONTNEED)`, so the pages are only mapped again when the
+    /// vector grows back into them. Unlike [`GenericVec::shrink_to_fit`], later
+    /// pushes up to the current capacity won't need to remap the storage.
+    pub fn release_spare(&mut self) {
+        if size_of::<T>() != 0 {
+            let len = self.len() * size_of::<T>();
+            self.storage.discard_from(len);
+        }
+    }
+}
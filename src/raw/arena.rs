@@ -0,0 +1,243 @@
+use crate::raw::Storage;
+
+use core::{
+    cell::Cell,
+    marker::PhantomData,
+    mem::{align_of, size_of, MaybeUninit},
+    ptr::NonNull,
+};
+
+/// A region of memory that can be shared by many [`Arena`] storages
+///
+/// Memory is handed out from the front of the region, like a bump allocator.
+/// Memory is only reclaimed if the most recent allocation is freed,
+/// or if the whole region is [`reset`](ArenaBuffer::reset).
+///
+/// ```rust
+/// use core::mem::MaybeUninit;
+/// use generic_vec::{GenericVec, raw::{Arena, ArenaBuffer}};
+///
+/// let mut buffer = [MaybeUninit::uninit(); 64];
+/// let buffer = ArenaBuffer::new(&mut buffer);
+///
+/// let mut a = GenericVec::with_storage(Arena::<u32>::new(&buffer));
+/// let mut b = GenericVec::with_storage(Arena::<u8>::new(&buffer));
+///
+/// a.extend_from_slice(&[1, 2, 3]);
+/// b.extend_from_slice(b"hello");
+///
+/// assert_eq!(a, [1, 2, 3]);
+/// assert_eq!(b, *b"hello");
+/// ```
+///
+/// `ArenaBuffer` keeps track of the allocated bytes in a `Cell`, so it is not `Sync`,
+/// and all of the [`Arena`]s that share a buffer must stay on the same thread.
+pub struct ArenaBuffer<'a> {
+    ptr: NonNull<u8>,
+    len: usize,
+    used: Cell<usize>,
+    mark: PhantomData<&'a mut [MaybeUninit<u8>]>,
+}
+
+/// A storage that carves out its capacity from an [`ArenaBuffer`]
+///
+/// If this storage holds the most recent allocation in the buffer it can grow in place,
+/// otherwise it will copy it's contents to a new, larger, allocation. The old
+/// allocation isn't reclaimed until the buffer is reset.
+///
+/// If the buffer runs out of space, `try_reserve` will fail, and `reserve` will panic.
+pub struct Arena<'b, 'a, T> {
+    buffer: &'b ArenaBuffer<'a>,
+    ptr: NonNull<T>,
+    capacity: usize,
+}
+
+enum OnFailure {
+    Abort,
+    Error,
+}
+
+impl<'a> ArenaBuffer<'a> {
+    /// Create a new `ArenaBuffer` that allocates out of the given slice
+    pub fn new(buffer: &'a mut [MaybeUninit<u8>]) -> Self {
+        Self {
+            len: buffer.len(),
+            ptr: NonNull::from(buffer).cast(),
+            used: Cell::new(0),
+            mark: PhantomData,
+        }
+    }
+
+    /// The total number of bytes in this buffer
+    pub fn capacity(&self) -> usize { self.len }
+
+    /// The number of bytes that haven't been allocated yet
+    pub fn remaining(&self) -> usize { self.len - self.used.get() }
+
+    /// Free all allocations in this buffer
+    ///
+    /// This takes `&mut self`, so there can't be any [`Arena`]s still using this buffer
+    pub fn reset(&mut self) { self.used.set(0) }
+
+    /// Allocate `size` bytes aligned to `align`
+    fn alloc(&self, size: usize, align: usize) -> Option<NonNull<u8>> {
+        let used = self.used.get();
+        let top = (self.ptr.as_ptr() as usize).checked_add(used)?;
+        let offset = used + (top.wrapping_neg() & (align - 1));
+        let end = offset.checked_add(size)?;
+
+        if end > self.len {
+            return None
+        }
+
+        self.used.set(end);
+
+        // Safety
+        //
+        // `offset <= end <= len`, so this pointer is in bounds of the buffer
+        unsafe { Some(NonNull::new_unchecked(self.ptr.as_ptr().add(offset))) }
+    }
+
+    /// The offset of `ptr` from the start of this buffer
+    fn offset_of(&self, ptr: NonNull<u8>) -> usize { (ptr.as_ptr() as usize).wrapping_sub(self.ptr.as_ptr() as usize) }
+
+    /// Try to grow the allocation at `ptr` from `old_size` to `new_size` bytes, without moving it
+    ///
+    /// This is only possible if it's the most recent allocation
+    fn grow_in_place(&self, ptr: NonNull<u8>, old_size: usize, new_size: usize) -> bool {
+        let offset = self.offset_of(ptr);
+
+        if offset.wrapping_add(old_size) != self.used.get() {
+            return false
+        }
+
+        match offset.checked_add(new_size) {
+            Some(end) if end <= self.len => {
+                self.used.set(end);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Free the allocation at `ptr` if it's the most recent allocation
+    fn free(&self, ptr: NonNull<u8>, size: usize) {
+        let offset = self.offset_of(ptr);
+
+        if offset.wrapping_add(size) == self.used.get() {
+            self.used.set(offset);
+        }
+    }
+}
+
+impl<'b, 'a, T> Arena<'b, 'a, T> {
+    /// Create a new zero-capacity storage that will allocate out of `buffer`
+    pub fn new(buffer: &'b ArenaBuffer<'a>) -> Self {
+        Self {
+            buffer,
+            ptr: NonNull::dangling(),
+            capacity: if size_of::<T>() == 0 { usize::MAX } else { 0 },
+        }
+    }
+
+    /// The buffer this storage allocates out of
+    pub fn buffer(&self) -> &'b ArenaBuffer<'a> { self.buffer }
+
+    #[cold]
+    #[inline(never)]
+    fn reserve_slow(&mut self, new_capacity: usize, on_failure: OnFailure) -> bool {
+        assert!(new_capacity > self.capacity);
+
+        // try to grow by doubling, but arenas are usually small, so
+        // fall back to growing exactly as much as requested
+        let doubled = new_capacity.max(self.capacity.saturating_mul(2));
+
+        if self.try_grow(doubled) || (doubled != new_capacity && self.try_grow(new_capacity)) {
+            return true
+        }
+
+        match on_failure {
+            OnFailure::Abort => out_of_memory(new_capacity, self.buffer.remaining()),
+            OnFailure::Error => false,
+        }
+    }
+
+    fn try_grow(&mut self, new_capacity: usize) -> bool {
+        let new_size = match new_capacity.checked_mul(size_of::<T>()) {
+            Some(size) => size,
+            None => return false,
+        };
+        let old_size = self.capacity * size_of::<T>();
+
+        if self.capacity != 0 && self.buffer.grow_in_place(self.ptr.cast(), old_size, new_size) {
+            self.capacity = new_capacity;
+            return true
+        }
+
+        let ptr = match self.buffer.alloc(new_size, align_of::<T>()) {
+            Some(ptr) => ptr,
+            None => return false,
+        };
+
+        // Safety
+        //
+        // * the new allocation comes after the old allocation in the buffer,
+        //   so they can't overlap
+        // * the new allocation is larger than the old allocation
+        unsafe {
+            ptr.as_ptr()
+                .copy_from_nonoverlapping(self.ptr.as_ptr().cast(), old_size)
+        }
+
+        self.ptr = ptr.cast();
+        self.capacity = new_capacity;
+
+        true
+    }
+}
+
+#[cold]
+#[inline(never)]
+fn out_of_memory(new_capacity: usize, remaining: usize) -> ! {
+    panic!(
+        "Tried to reserve {}, but the arena only has {} bytes remaining",
+        new_capacity, remaining
+    )
+}
+
+impl<T> Drop for Arena<'_, '_, T> {
+    fn drop(&mut self) {
+        if self.capacity != 0 && size_of::<T>() != 0 {
+            self.buffer.free(self.ptr.cast(), self.capacity * size_of::<T>())
+        }
+    }
+}
+
+unsafe impl<T> Storage<T> for Arena<'_, '_, T> {
+    const IS_ALIGNED: bool = true;
+
+    #[inline]
+    fn capacity(&self) -> usize { self.capacity }
+
+    #[inline]
+    fn as_ptr(&self) -> *const T { self.ptr.as_ptr() }
+
+    #[inline]
+    fn as_mut_ptr(&mut self) -> *mut T { self.ptr.as_ptr() }
+
+    #[inline]
+    fn reserve(&mut self, new_capacity: usize) {
+        if self.capacity < new_capacity {
+            let _ = self.reserve_slow(new_capacity, OnFailure::Abort);
+        }
+    }
+
+    #[inline]
+    fn try_reserve(&mut self, new_capacity: usize) -> bool {
+        if self.capacity < new_capacity {
+            self.reserve_slow(new_capacity, OnFailure::Error)
+        } else {
+            true
+        }
+    }
+}
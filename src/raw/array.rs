@@ -1,5 +1,5 @@
-#[cfg(feature = "nightly")]
-mod nightly;
+#[cfg(not(no_const_generics))]
+mod const_generic;
 
-#[cfg(not(feature = "nightly"))]
-mod stable;
+#[cfg(no_const_generics)]
+mod legacy;
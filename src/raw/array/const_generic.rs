@@ -1,4 +1,4 @@
-use crate::raw::{Storage, StorageWithCapacity};
+use crate::raw::{FixedCapacity, Storage, StorageWithCapacity};
 
 unsafe impl<T: Copy, const N: usize> crate::raw::StorageInit<T> for [T; N] {}
 unsafe impl<T: Default + Copy, const N: usize> StorageWithCapacity<T> for [T; N]
@@ -43,3 +43,7 @@ unsafe impl<T: Copy, const N: usize> Storage<T> for [T; N] {
 
     fn try_reserve(&mut self, capacity: usize) -> bool { capacity <= N }
 }
+
+unsafe impl<T: Copy, const N: usize> FixedCapacity<T> for [T; N] {
+    const CAPACITY: usize = N;
+}
@@ -1,4 +1,4 @@
-use crate::raw::{Storage, StorageWithCapacity};
+use crate::raw::{FixedCapacity, Storage, StorageWithCapacity};
 
 macro_rules! array {
     ($($N:expr),*) => {
@@ -47,6 +47,10 @@ macro_rules! array {
                 capacity <= $N
             }
         }
+
+        unsafe impl<T: Copy> FixedCapacity<T> for [T; $N] {
+            const CAPACITY: usize = $N;
+        }
     )*};
 }
 
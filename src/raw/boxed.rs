@@ -0,0 +1,138 @@
+use crate::raw::{Storage, StorageWithCapacity};
+
+use core::mem::{size_of, ManuallyDrop, MaybeUninit};
+use std::{boxed::Box, vec::Vec};
+
+/// A heap storage with a fixed capacity that is chosen at runtime
+///
+/// Unlike [`Heap`](crate::raw::Heap), this storage never reallocates. It allocates exactly
+/// once, when it's created, and reserving more than its capacity will fail.
+///
+/// ```rust
+/// use generic_vec::{GenericVec, raw::BoxedSlice};
+///
+/// let mut vec = GenericVec::<i32, BoxedSlice<i32>>::with_capacity(3);
+/// vec.extend_from_slice(&[1, 2, 3]);
+/// assert!(!vec.try_reserve(1));
+///
+/// // converting a full vector into a boxed slice doesn't reallocate
+/// let boxed: Box<[i32]> = vec.into();
+/// assert_eq!(*boxed, [1, 2, 3]);
+/// ```
+///
+/// Usable with the `alloc` feature
+pub struct BoxedSlice<T>(Box<[MaybeUninit<T>]>);
+
+impl<T> BoxedSlice<T> {
+    /// Allocate a new storage that can hold exactly `capacity` elements
+    pub fn new(capacity: usize) -> Self {
+        let mut vec = Vec::with_capacity(capacity);
+
+        // Safety
+        //
+        // `MaybeUninit` doesn't need to be initialized
+        unsafe { vec.set_len(capacity) }
+
+        Self(vec.into_boxed_slice())
+    }
+
+    /// Create a new storage that uses the given allocation
+    pub fn from_boxed(boxed: Box<[MaybeUninit<T>]>) -> Self { Self(boxed) }
+
+    /// Get the backing allocation of this storage
+    pub fn into_boxed(self) -> Box<[MaybeUninit<T>]> { self.0 }
+}
+
+impl<T> Default for BoxedSlice<T> {
+    fn default() -> Self { Self(Box::new([])) }
+}
+
+unsafe impl<T> Storage<T> for BoxedSlice<T> {
+    const IS_ALIGNED: bool = true;
+
+    #[inline]
+    fn capacity(&self) -> usize { Storage::<T>::capacity(&*self.0) }
+
+    #[inline]
+    fn as_ptr(&self) -> *const T { self.0.as_ptr().cast() }
+
+    #[inline]
+    fn as_mut_ptr(&mut self) -> *mut T { self.0.as_mut_ptr().cast() }
+
+    #[inline]
+    fn reserve(&mut self, new_capacity: usize) { Storage::<T>::reserve(&mut *self.0, new_capacity) }
+
+    #[inline]
+    fn try_reserve(&mut self, new_capacity: usize) -> bool { Storage::<T>::try_reserve(&mut *self.0, new_capacity) }
+}
+
+unsafe impl<T> StorageWithCapacity<T> for BoxedSlice<T> {
+    fn with_capacity(capacity: usize) -> Self { Self::new(capacity) }
+}
+
+unsafe impl<T> Storage<T> for Vec<MaybeUninit<T>> {
+    const IS_ALIGNED: bool = true;
+
+    #[inline]
+    fn capacity(&self) -> usize { self.capacity() }
+
+    #[inline]
+    fn as_ptr(&self) -> *const T { self.as_ptr().cast() }
+
+    #[inline]
+    fn as_mut_ptr(&mut self) -> *mut T { self.as_mut_ptr().cast() }
+
+    #[inline]
+    fn reserve(&mut self, new_capacity: usize) {
+        // the length of the `Vec` is ignored, so reserve relative to it to
+        // get a capacity of at least `new_capacity`
+        if self.capacity() < new_capacity {
+            self.reserve(new_capacity - self.len())
+        }
+    }
+
+    #[inline]
+    fn try_reserve(&mut self, new_capacity: usize) -> bool {
+        self.capacity() >= new_capacity || self.try_reserve(new_capacity - self.len()).is_ok()
+    }
+}
+
+unsafe impl<T> StorageWithCapacity<T> for Vec<MaybeUninit<T>> {
+    fn with_capacity(capacity: usize) -> Self { Vec::with_capacity(capacity) }
+}
+
+/// Convert an allocation with `len` initialized elements into a `Vec<T>`
+///
+/// # Safety
+///
+/// The first `len` elements of `boxed` must be initialized
+pub(crate) unsafe fn boxed_into_vec<T>(boxed: Box<[MaybeUninit<T>]>, len: usize) -> Vec<T> {
+    let mut boxed = ManuallyDrop::new(boxed);
+
+    // a `Vec` of zero-sized types always has a capacity of `usize::MAX`
+    let capacity = if size_of::<T>() == 0 { usize::MAX } else { boxed.len() };
+
+    // Safety
+    //
+    // * a `Box<[MaybeUninit<T>]>` is allocated with the same layout as
+    //   a `Vec<T>` with a capacity of it's length, or nothing is allocated
+    //   for zero-sized types
+    // * the first `len` elements are initialized
+    unsafe { Vec::from_raw_parts(boxed.as_mut_ptr().cast(), len, capacity) }
+}
+
+/// Convert a `Vec` with `len` initialized elements into a `Vec<T>`
+///
+/// # Safety
+///
+/// The first `len` elements of `vec` must be initialized
+pub(crate) unsafe fn uninit_into_vec<T>(vec: Vec<MaybeUninit<T>>, len: usize) -> Vec<T> {
+    let mut vec = ManuallyDrop::new(vec);
+    let capacity = vec.capacity();
+
+    // Safety
+    //
+    // * `MaybeUninit<T>` has the same layout as `T`
+    // * the first `len` elements are initialized
+    unsafe { Vec::from_raw_parts(vec.as_mut_ptr().cast(), len, capacity) }
+}
@@ -0,0 +1,268 @@
+use crate::raw::{Storage, StorageWithCapacity};
+
+use core::mem::size_of;
+
+/// The bytes written directly after the capacity of a [`Checked`] storage
+const CANARY: [u8; 16] = *b"GenericVecCanary";
+
+enum OnFailure {
+    Abort,
+    Error,
+}
+
+enum Growth {
+    Amortized,
+    Exact,
+}
+
+/// A storage adapter that helps catch memory corruption caused by `unsafe` code
+///
+/// * all spare capacity is filled with [`Checked::POISON`], so reading uninitialized
+///   elements gives a recognizable pattern. Elements removed from a
+///   [`GenericVec`](crate::GenericVec) are poisoned again.
+/// * a canary is placed directly after the capacity, and it's verified every time the
+///   length of the vector changes, when the storage grows, and when the storage is dropped.
+///   If something wrote past the capacity of the vector, this will panic.
+///
+/// The canary takes up some space in the underlying storage, so the capacity will be
+/// slightly smaller than the capacity of the underlying storage. `Checked` also needs to view
+/// the storage as bytes, so the storage must implement `Storage<u8>`.
+///
+/// With the `debug-checks` feature, the preconditions of [`GenericVec`](crate::GenericVec)'s
+/// `*_unchecked` methods are always checked, even in release mode.
+///
+/// Usable with the `debug-checks` feature
+///
+/// ```rust
+/// use generic_vec::{GenericVec, raw::{Checked, Heap}};
+///
+/// let mut vec = GenericVec::<u8, _>::with_storage(Checked::new(Heap::<u8>::new()));
+/// vec.extend_from_slice(b"hello");
+/// vec.truncate(4);
+///
+/// // the removed byte was poisoned
+/// assert_eq!(unsafe { *vec.as_ptr().add(4) }, Checked::<Heap<u8>>::POISON);
+/// ```
+pub struct Checked<S: ?Sized + Storage<u8>> {
+    armed: bool,
+    capacity_bytes: usize,
+    storage: S,
+}
+
+/// The number of `T`s needed to hold the canary
+const fn canary_len<T>() -> usize {
+    match size_of::<T>() {
+        0 => 0,
+        size => (CANARY.len() - 1) / size + 1,
+    }
+}
+
+impl<S: ?Sized + Storage<u8>> Drop for Checked<S> {
+    fn drop(&mut self) { self.verify() }
+}
+
+impl<S: Storage<u8>> Checked<S> {
+    /// Create a new `Checked` storage, wrapping the given storage
+    ///
+    /// The spare capacity is poisoned, and the canary is placed, the first time the storage is reserved
+    pub const fn new(storage: S) -> Self {
+        Self {
+            armed: false,
+            capacity_bytes: 0,
+            storage,
+        }
+    }
+}
+
+impl<S: ?Sized + Storage<u8>> Checked<S> {
+    /// The byte pattern that fills unused memory
+    pub const POISON: u8 = 0xA5;
+
+    /// Get a reference to the underlying storage
+    pub fn get(&self) -> &S { &self.storage }
+
+    /// Panics if the canary after the capacity was overwritten
+    pub fn verify(&self) {
+        if !self.armed {
+            return
+        }
+
+        // Safety
+        //
+        // the canary was written directly after the capacity, and the
+        // underlying storage has room for it
+        let canary = unsafe {
+            core::slice::from_raw_parts(
+                Storage::<u8>::as_ptr(&self.storage).add(self.capacity_bytes),
+                CANARY.len(),
+            )
+        };
+
+        if canary != CANARY {
+            canary_error(self.capacity_bytes)
+        }
+    }
+
+    /// Poison the new spare capacity, and place the canary after the capacity
+    /// of the underlying storage
+    fn arm<T>(&mut self)
+    where
+        S: Storage<T>,
+    {
+        // zero-sized types don't use any memory, so there's nothing to check
+        if size_of::<T>() == 0 {
+            return
+        }
+
+        let inner_bytes = Storage::<u8>::capacity(&self.storage);
+
+        let capacity = match inner_bytes.checked_sub(CANARY.len()) {
+            Some(bytes) => bytes / size_of::<T>(),
+            None => return,
+        };
+
+        let start = if self.armed { self.capacity_bytes } else { 0 };
+        let capacity_bytes = capacity * size_of::<T>();
+
+        // Safety
+        //
+        // * everything past the old capacity is spare, or the old canary
+        // * the new canary fits in the underlying storage
+        unsafe {
+            let ptr = Storage::<u8>::as_mut_ptr(&mut self.storage);
+            ptr.add(start).write_bytes(Self::POISON, inner_bytes - start);
+            ptr.add(capacity_bytes)
+                .copy_from_nonoverlapping(CANARY.as_ptr(), CANARY.len());
+        }
+
+        self.armed = true;
+        self.capacity_bytes = capacity_bytes;
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn reserve_slow<T>(&mut self, new_capacity: usize, growth: Growth, on_failure: OnFailure) -> bool
+    where
+        S: Storage<T>,
+    {
+        self.verify();
+
+        let inner_capacity = new_capacity.checked_add(canary_len::<T>());
+
+        match (inner_capacity, on_failure) {
+            (Some(inner_capacity), OnFailure::Abort) => match growth {
+                Growth::Amortized => Storage::<T>::reserve(&mut self.storage, inner_capacity),
+                Growth::Exact => Storage::<T>::__reserve_exact__(&mut self.storage, inner_capacity),
+            },
+            (Some(inner_capacity), OnFailure::Error) => {
+                let reserved = match growth {
+                    Growth::Amortized => Storage::<T>::try_reserve(&mut self.storage, inner_capacity),
+                    Growth::Exact => Storage::<T>::__try_reserve_exact__(&mut self.storage, inner_capacity),
+                };
+
+                if !reserved {
+                    return false
+                }
+            }
+            (None, OnFailure::Abort) => panic!("Capacity overflow"),
+            (None, OnFailure::Error) => return false,
+        }
+
+        self.arm::<T>();
+
+        true
+    }
+}
+
+#[cold]
+#[inline(never)]
+fn canary_error(offset: usize) -> ! {
+    panic!(
+        "The canary at byte offset {} was overwritten, something wrote past the capacity of the vector",
+        offset
+    )
+}
+
+impl<S: Storage<u8> + Default> Default for Checked<S> {
+    fn default() -> Self { Self::new(S::default()) }
+}
+
+unsafe impl<T, S: ?Sized + Storage<T> + Storage<u8>> Storage<T> for Checked<S> {
+    const IS_ALIGNED: bool = <S as Storage<T>>::IS_ALIGNED;
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        match size_of::<T>() {
+            0 => Storage::<T>::capacity(&self.storage),
+            size => self.capacity_bytes / size,
+        }
+    }
+
+    #[inline]
+    fn as_ptr(&self) -> *const T { Storage::<T>::as_ptr(&self.storage) }
+
+    #[inline]
+    fn as_mut_ptr(&mut self) -> *mut T { Storage::<T>::as_mut_ptr(&mut self.storage) }
+
+    #[inline]
+    fn reserve(&mut self, new_capacity: usize) {
+        if Storage::<T>::capacity(self) < new_capacity {
+            self.reserve_slow::<T>(new_capacity, Growth::Amortized, OnFailure::Abort);
+        }
+    }
+
+    #[inline]
+    fn try_reserve(&mut self, new_capacity: usize) -> bool {
+        Storage::<T>::capacity(self) >= new_capacity
+            || self.reserve_slow::<T>(new_capacity, Growth::Amortized, OnFailure::Error)
+    }
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    fn __reserve_exact__(&mut self, new_capacity: usize) {
+        if Storage::<T>::capacity(self) < new_capacity {
+            self.reserve_slow::<T>(new_capacity, Growth::Exact, OnFailure::Abort);
+        }
+    }
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    fn __try_reserve_exact__(&mut self, new_capacity: usize) -> bool {
+        Storage::<T>::capacity(self) >= new_capacity
+            || self.reserve_slow::<T>(new_capacity, Growth::Exact, OnFailure::Error)
+    }
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    unsafe fn __wipe__(&mut self, index: usize, len: usize) {
+        unsafe {
+            Storage::<T>::__wipe__(&mut self.storage, index, len);
+            Storage::<T>::as_mut_ptr(&mut self.storage)
+                .add(index)
+                .cast::<u8>()
+                .write_bytes(Self::POISON, len * size_of::<T>());
+        }
+    }
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    fn __verify__(&self) {
+        Storage::<T>::__verify__(&self.storage);
+        self.verify();
+    }
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    unsafe fn __on_drop__(&mut self, len: usize) -> bool {
+        unsafe { Storage::<T>::__on_drop__(&mut self.storage, len) }
+    }
+}
+
+unsafe impl<T, S: StorageWithCapacity<T> + Storage<u8>> StorageWithCapacity<T> for Checked<S> {
+    fn with_capacity(capacity: usize) -> Self {
+        let inner_capacity = capacity.checked_add(canary_len::<T>()).expect("Capacity overflow");
+        let mut storage = Self::new(S::with_capacity(inner_capacity));
+        storage.arm::<T>();
+        storage
+    }
+}
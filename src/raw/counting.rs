@@ -0,0 +1,232 @@
+use crate::raw::{FixedCapacity, Storage, StorageInit, StorageWithCapacity};
+
+/// Statistics collected by a [`Counting`] storage
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CountingStats {
+    /// The number of times `reserve` or `try_reserve` was called
+    pub reserve_calls: usize,
+    /// The number of times the storage had to grow to satisfy a reserve
+    pub reallocations: usize,
+    /// The largest capacity the storage has had
+    pub peak_capacity: usize,
+    /// The largest number of unused slots right after the storage grew,
+    /// i.e. how much capacity it allocated past what was requested
+    pub peak_unused_capacity: usize,
+    /// The number of times `try_reserve` failed
+    pub failed_try_reserves: usize,
+}
+
+/// An event that is passed to the hook of a [`Counting`] storage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CountingEvent {
+    /// The storage grew from `old_capacity` to `new_capacity`
+    Grow {
+        /// The capacity before the storage grew
+        old_capacity: usize,
+        /// The capacity after the storage grew
+        new_capacity: usize,
+    },
+    /// A `try_reserve` for `new_capacity` elements failed
+    TryReserveFailed {
+        /// The capacity of the storage
+        capacity: usize,
+        /// The requested capacity
+        new_capacity: usize,
+    },
+}
+
+/// A storage adapter that counts how often the storage grows
+///
+/// The statistics can be read with [`Counting::stats`], which is reachable through
+/// [`GenericVec::storage`](crate::GenericVec::storage). A hook can be set with
+/// [`Counting::with_hook`] to observe each growth event as it happens. The hook is a plain
+/// function pointer, so it's also usable without the `alloc` feature.
+///
+/// ```rust
+/// use generic_vec::{GenericVec, raw::{Counting, Heap}};
+///
+/// let mut vec = GenericVec::with_storage(Counting::new(Heap::<u32>::new()));
+/// for i in 0..100 {
+///     vec.push(i);
+/// }
+///
+/// let stats = vec.storage().stats();
+/// assert!(stats.reallocations > 1);
+/// assert!(stats.peak_capacity >= 100);
+/// assert_eq!(stats.failed_try_reserves, 0);
+/// ```
+pub struct Counting<S: ?Sized> {
+    stats: CountingStats,
+    hook: Option<fn(CountingEvent)>,
+    storage: S,
+}
+
+impl<S> Counting<S> {
+    /// Create a new `Counting` storage, wrapping the given storage
+    ///
+    /// This is a `const fn`, so it can't ask `storage` for its capacity. The initial
+    /// capacity is only counted towards [`CountingStats::peak_capacity`] on the first
+    /// reserve. [`StorageWithCapacity::with_capacity`] counts it right away.
+    pub const fn new(storage: S) -> Self {
+        Self {
+            stats: CountingStats {
+                reserve_calls: 0,
+                reallocations: 0,
+                peak_capacity: 0,
+                peak_unused_capacity: 0,
+                failed_try_reserves: 0,
+            },
+            hook: None,
+            storage,
+        }
+    }
+
+    /// Call `hook` every time the storage grows, or a `try_reserve` fails
+    pub const fn with_hook(mut self, hook: fn(CountingEvent)) -> Self {
+        self.hook = Some(hook);
+        self
+    }
+
+    /// Get the underlying storage
+    pub fn into_inner(self) -> S { self.storage }
+
+    /// Wrap a freshly created storage, counting its initial capacity towards the peak
+    fn from_storage<T>(storage: S) -> Self
+    where
+        S: Storage<T>,
+    {
+        let mut counting = Self::new(storage);
+        counting.stats.peak_capacity = counting.storage.capacity();
+        counting
+    }
+}
+
+impl<S: ?Sized> Counting<S> {
+    /// Get a reference to the underlying storage
+    pub fn get(&self) -> &S { &self.storage }
+
+    /// Get the statistics collected so far
+    pub fn stats(&self) -> CountingStats { self.stats }
+
+    /// Reset all statistics to zero
+    pub fn reset_stats(&mut self) { self.stats = CountingStats::default() }
+
+    fn record_capacity(&mut self, old_capacity: usize, capacity: usize, requested: usize) {
+        self.stats.peak_capacity = self.stats.peak_capacity.max(old_capacity).max(capacity);
+
+        if capacity != old_capacity {
+            self.stats.reallocations += 1;
+            self.stats.peak_unused_capacity = self.stats.peak_unused_capacity.max(capacity.saturating_sub(requested));
+            self.emit(CountingEvent::Grow {
+                old_capacity,
+                new_capacity: capacity,
+            });
+        }
+    }
+
+    fn record_failure(&mut self, capacity: usize, new_capacity: usize) {
+        self.stats.failed_try_reserves += 1;
+        self.emit(CountingEvent::TryReserveFailed { capacity, new_capacity });
+    }
+
+    fn emit(&self, event: CountingEvent) {
+        if let Some(hook) = self.hook {
+            hook(event)
+        }
+    }
+}
+
+impl<S: Default> Default for Counting<S> {
+    fn default() -> Self { Self::new(S::default()) }
+}
+
+unsafe impl<T, S: ?Sized + StorageInit<T>> StorageInit<T> for Counting<S> {}
+unsafe impl<T, S: ?Sized + Storage<T>> Storage<T> for Counting<S> {
+    #[doc(hidden)]
+    const CONST_CAPACITY: Option<usize> = S::CONST_CAPACITY;
+    const IS_ALIGNED: bool = S::IS_ALIGNED;
+
+    #[inline]
+    fn capacity(&self) -> usize { self.storage.capacity() }
+
+    #[inline]
+    fn as_ptr(&self) -> *const T { self.storage.as_ptr() }
+
+    #[inline]
+    fn as_mut_ptr(&mut self) -> *mut T { self.storage.as_mut_ptr() }
+
+    fn reserve(&mut self, new_capacity: usize) {
+        self.stats.reserve_calls += 1;
+        let old_capacity = self.storage.capacity();
+        self.storage.reserve(new_capacity);
+        self.record_capacity(old_capacity, self.storage.capacity(), new_capacity);
+    }
+
+    fn try_reserve(&mut self, new_capacity: usize) -> bool {
+        self.stats.reserve_calls += 1;
+        let old_capacity = self.storage.capacity();
+
+        if self.storage.try_reserve(new_capacity) {
+            self.record_capacity(old_capacity, self.storage.capacity(), new_capacity);
+            true
+        } else {
+            self.record_failure(old_capacity, new_capacity);
+            false
+        }
+    }
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    fn __reserve_exact__(&mut self, new_capacity: usize) {
+        self.stats.reserve_calls += 1;
+        let old_capacity = self.storage.capacity();
+        self.storage.__reserve_exact__(new_capacity);
+        self.record_capacity(old_capacity, self.storage.capacity(), new_capacity);
+    }
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    fn __try_reserve_exact__(&mut self, new_capacity: usize) -> bool {
+        self.stats.reserve_calls += 1;
+        let old_capacity = self.storage.capacity();
+
+        if self.storage.__try_reserve_exact__(new_capacity) {
+            self.record_capacity(old_capacity, self.storage.capacity(), new_capacity);
+            true
+        } else {
+            self.record_failure(old_capacity, new_capacity);
+            false
+        }
+    }
+
+    #[doc(hidden)]
+    #[inline(always)]
+    #[allow(non_snake_case)]
+    unsafe fn __wipe__(&mut self, index: usize, len: usize) { unsafe { self.storage.__wipe__(index, len) } }
+
+    #[doc(hidden)]
+    #[inline(always)]
+    #[allow(non_snake_case)]
+    fn __verify__(&self) { self.storage.__verify__() }
+
+    #[doc(hidden)]
+    #[inline(always)]
+    #[allow(non_snake_case)]
+    unsafe fn __on_drop__(&mut self, len: usize) -> bool { unsafe { self.storage.__on_drop__(len) } }
+}
+
+unsafe impl<T, S: ?Sized + FixedCapacity<T>> FixedCapacity<T> for Counting<S> {
+    const CAPACITY: usize = S::CAPACITY;
+}
+
+unsafe impl<T, S: StorageWithCapacity<T>> StorageWithCapacity<T> for Counting<S> {
+    #[inline]
+    fn with_capacity(capacity: usize) -> Self { Self::from_storage(S::with_capacity(capacity)) }
+
+    #[doc(hidden)]
+    #[inline]
+    #[allow(non_snake_case)]
+    fn __with_capacity__const_capacity_checked(capacity: usize, old_capacity: Option<usize>) -> Self {
+        Self::from_storage(S::__with_capacity__const_capacity_checked(capacity, old_capacity))
+    }
+}
@@ -0,0 +1,152 @@
+use crate::raw::Storage;
+
+/// An object safe version of [`Storage`]
+///
+/// [`Storage`] has associated constants, so it can't be used as a trait object.
+/// `DynStorage` is implemented for every [`Storage`], and `dyn DynStorage<T>`
+/// implements [`Storage`], so a [`GenericVec`](crate::GenericVec) of any storage can be
+/// used as a `GenericVec<T, dyn DynStorage<T>>`. This lets functions accept any vector,
+/// without being generic over the storage.
+///
+/// ```rust
+/// use generic_vec::{ArrayVec, DynVec, HeapVec};
+///
+/// fn push_twice(vec: &mut DynVec<'_, i32>, value: i32) {
+///     vec.push(value);
+///     vec.push(value);
+/// }
+///
+/// let mut array_vec = ArrayVec::<i32, 4>::new();
+/// let mut heap_vec = HeapVec::new();
+///
+/// push_twice(&mut array_vec, 1);
+/// push_twice(heap_vec.as_dyn_mut(), 2);
+///
+/// assert_eq!(array_vec, [1, 1]);
+/// assert_eq!(heap_vec, [2, 2]);
+/// ```
+///
+/// # Safety
+///
+/// The methods must behave like the corresponding methods of [`Storage`]
+pub unsafe trait DynStorage<T> {
+    /// The number of elements that it is valid to write to this storage,
+    /// see [`Storage::capacity`]
+    fn dyn_capacity(&self) -> usize;
+
+    /// Returns a pointer to the first element, see [`Storage::as_ptr`]
+    fn dyn_as_ptr(&self) -> *const T;
+
+    /// Returns a mutable pointer to the first element, see [`Storage::as_mut_ptr`]
+    fn dyn_as_mut_ptr(&mut self) -> *mut T;
+
+    /// Reserves space for at least `new_capacity` elements, see [`Storage::reserve`]
+    fn dyn_reserve(&mut self, new_capacity: usize);
+
+    /// Tries to reserve space for at least `new_capacity` elements, see [`Storage::try_reserve`]
+    fn dyn_try_reserve(&mut self, new_capacity: usize) -> bool;
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    fn __dyn_reserve_exact__(&mut self, new_capacity: usize);
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    fn __dyn_try_reserve_exact__(&mut self, new_capacity: usize) -> bool;
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    unsafe fn __dyn_wipe__(&mut self, index: usize, len: usize);
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    fn __dyn_verify__(&self);
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    unsafe fn __dyn_on_drop__(&mut self, len: usize) -> bool;
+}
+
+unsafe impl<T, S: ?Sized + Storage<T>> DynStorage<T> for S {
+    #[inline]
+    fn dyn_capacity(&self) -> usize { self.capacity() }
+
+    #[inline]
+    fn dyn_as_ptr(&self) -> *const T { self.as_ptr() }
+
+    #[inline]
+    fn dyn_as_mut_ptr(&mut self) -> *mut T { self.as_mut_ptr() }
+
+    #[inline]
+    fn dyn_reserve(&mut self, new_capacity: usize) { self.reserve(new_capacity) }
+
+    #[inline]
+    fn dyn_try_reserve(&mut self, new_capacity: usize) -> bool { self.try_reserve(new_capacity) }
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    fn __dyn_reserve_exact__(&mut self, new_capacity: usize) { self.__reserve_exact__(new_capacity) }
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    fn __dyn_try_reserve_exact__(&mut self, new_capacity: usize) -> bool { self.__try_reserve_exact__(new_capacity) }
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    unsafe fn __dyn_wipe__(&mut self, index: usize, len: usize) { unsafe { self.__wipe__(index, len) } }
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    fn __dyn_verify__(&self) { self.__verify__() }
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    unsafe fn __dyn_on_drop__(&mut self, len: usize) -> bool { unsafe { self.__on_drop__(len) } }
+}
+
+unsafe impl<T> Storage<T> for dyn DynStorage<T> + '_ {
+    /// The alignment of the underlying storage isn't known, so `with_storage`
+    /// will reject boxed trait objects. Vectors that are unsized to use a
+    /// `dyn DynStorage<T>` were already checked when they were created.
+    const IS_ALIGNED: bool = false;
+
+    #[inline]
+    fn capacity(&self) -> usize { self.dyn_capacity() }
+
+    #[inline]
+    fn as_ptr(&self) -> *const T { self.dyn_as_ptr() }
+
+    #[inline]
+    fn as_mut_ptr(&mut self) -> *mut T { self.dyn_as_mut_ptr() }
+
+    #[inline]
+    fn reserve(&mut self, new_capacity: usize) { self.dyn_reserve(new_capacity) }
+
+    #[inline]
+    fn try_reserve(&mut self, new_capacity: usize) -> bool { self.dyn_try_reserve(new_capacity) }
+
+    #[doc(hidden)]
+    #[inline]
+    #[allow(non_snake_case)]
+    fn __reserve_exact__(&mut self, new_capacity: usize) { self.__dyn_reserve_exact__(new_capacity) }
+
+    #[doc(hidden)]
+    #[inline]
+    #[allow(non_snake_case)]
+    fn __try_reserve_exact__(&mut self, new_capacity: usize) -> bool { self.__dyn_try_reserve_exact__(new_capacity) }
+
+    #[doc(hidden)]
+    #[inline]
+    #[allow(non_snake_case)]
+    unsafe fn __wipe__(&mut self, index: usize, len: usize) { unsafe { self.__dyn_wipe__(index, len) } }
+
+    #[doc(hidden)]
+    #[inline]
+    #[allow(non_snake_case)]
+    fn __verify__(&self) { self.__dyn_verify__() }
+
+    #[doc(hidden)]
+    #[inline]
+    #[allow(non_snake_case)]
+    unsafe fn __on_drop__(&mut self, len: usize) -> bool { unsafe { self.__dyn_on_drop__(len) } }
+}
@@ -7,11 +7,15 @@ macro_rules! doc_heap {
     }
 }
 
+#[cfg(any(doc, not(no_const_generics)))]
+mod aligned;
 #[cfg(any(doc, feature = "nightly"))]
 mod nightly;
 #[cfg(not(any(doc, feature = "nightly")))]
 mod stable;
 
+#[cfg(any(doc, not(no_const_generics)))]
+pub use aligned::AlignedHeap;
 #[cfg(any(doc, feature = "nightly"))]
 pub use nightly::Heap;
 #[cfg(not(any(doc, feature = "nightly")))]
@@ -0,0 +1,232 @@
+use crate::raw::{Heap, Storage, StorageWithCapacity};
+
+use core::{
+    alloc::Layout,
+    mem::{align_of, size_of, ManuallyDrop},
+    ptr::NonNull,
+};
+use std::alloc::{alloc, dealloc, handle_alloc_error, realloc};
+
+/// A heap storage that allocates with an alignment of at least `ALIGN`
+///
+/// The elements are always aligned to the larger of `ALIGN` and the alignment of `T`,
+/// even when the storage is empty. This is useful for SIMD and DMA buffers, which need
+/// a larger alignment than their element type.
+///
+/// ```rust
+/// use generic_vec::AlignedHeapVec;
+///
+/// let mut vec = AlignedHeapVec::<u8, 64>::new();
+/// vec.extend_from_slice(b"hello world");
+/// assert_eq!(vec.as_ptr() as usize % 64, 0);
+/// ```
+///
+/// `ALIGN` must be a power of two, otherwise using this storage will fail to compile
+///
+/// Usable with the `alloc` feature
+pub struct AlignedHeap<T, const ALIGN: usize> {
+    capacity: usize,
+    ptr: NonNull<T>,
+}
+
+unsafe impl<T: Send, const ALIGN: usize> Send for AlignedHeap<T, ALIGN> {}
+unsafe impl<T: Sync, const ALIGN: usize> Sync for AlignedHeap<T, ALIGN> {}
+
+enum OnFailure {
+    Abort,
+    Error,
+}
+
+impl<T, const ALIGN: usize> AlignedHeap<T, ALIGN> {
+    /// The alignment of the allocation
+    pub const LAYOUT_ALIGN: usize = {
+        assert!(ALIGN.is_power_of_two(), "The alignment must be a power of two");
+
+        if ALIGN > align_of::<T>() {
+            ALIGN
+        } else {
+            align_of::<T>()
+        }
+    };
+
+    /// A well aligned pointer for an empty storage
+    const fn dangling() -> NonNull<T> {
+        // Safety
+        //
+        // the alignment is never zero
+        unsafe { NonNull::new_unchecked(Self::LAYOUT_ALIGN as *mut T) }
+    }
+
+    fn layout(capacity: usize) -> Option<Layout> {
+        let size = capacity.checked_mul(size_of::<T>())?;
+        Layout::from_size_align(size, Self::LAYOUT_ALIGN).ok()
+    }
+
+    /// Create a new zero-capacity heap storage
+    pub const fn new() -> Self {
+        Self {
+            ptr: Self::dangling(),
+            capacity: if size_of::<T>() == 0 { usize::MAX } else { 0 },
+        }
+    }
+
+    /// Create a new `AlignedHeap` storage from the given pointer and capacity
+    ///
+    /// # Safety
+    ///
+    /// If the capacity is non-zero
+    /// * You must have allocated the pointer from the global allocator
+    ///   with an alignment of `Self::LAYOUT_ALIGN`
+    /// * The pointer must be valid to read-write for the range `ptr..ptr.add(capacity)`
+    pub unsafe fn from_raw_parts(ptr: NonNull<T>, capacity: usize) -> Self { Self { ptr, capacity } }
+
+    /// Convert an `AlignedHeap` storage into a pointer and capacity, without
+    /// deallocating the storage
+    pub fn into_raw_parts(self) -> (NonNull<T>, usize) {
+        let this = ManuallyDrop::new(self);
+        (this.ptr, this.capacity)
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        if size_of::<T>() == 0 || capacity == 0 {
+            return Self::new()
+        }
+
+        let layout = Self::layout(capacity).expect("Invalid layout");
+
+        let ptr = match NonNull::new(unsafe { alloc(layout) }) {
+            Some(ptr) => ptr,
+            None => handle_alloc_error(layout),
+        };
+
+        Self {
+            ptr: ptr.cast(),
+            capacity,
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn reserve_slow(&mut self, new_capacity: usize, on_failure: OnFailure) -> bool {
+        assert!(new_capacity > self.capacity);
+
+        // grow by at least doubling
+        let new_capacity = new_capacity
+            .max(self.capacity.checked_mul(2).expect("Could not grow further"))
+            .max(super::INIT_ALLOC_CAPACITY);
+        let layout = Self::layout(new_capacity).expect("Invalid layout");
+
+        let ptr = if self.capacity == 0 {
+            unsafe { alloc(layout) }
+        } else {
+            // Safety
+            //
+            // the old layout was valid when the storage was allocated
+            let old_layout =
+                unsafe { Layout::from_size_align_unchecked(self.capacity * size_of::<T>(), Self::LAYOUT_ALIGN) };
+
+            // `realloc` keeps the alignment of the old layout
+            unsafe { realloc(self.ptr.as_ptr().cast(), old_layout, layout.size()) }
+        };
+
+        let ptr = match (NonNull::new(ptr), on_failure) {
+            (Some(ptr), _) => ptr,
+            (None, OnFailure::Abort) => handle_alloc_error(layout),
+            (None, OnFailure::Error) => return false,
+        };
+
+        self.ptr = ptr.cast();
+        self.capacity = new_capacity;
+
+        true
+    }
+}
+
+impl<T, const ALIGN: usize> Drop for AlignedHeap<T, ALIGN> {
+    fn drop(&mut self) {
+        let size = size_of::<T>().wrapping_mul(self.capacity);
+
+        // zero-sized allocations are never made, so there's nothing to free
+        if size != 0 {
+            unsafe {
+                dealloc(
+                    self.ptr.as_ptr().cast(),
+                    Layout::from_size_align_unchecked(size, Self::LAYOUT_ALIGN),
+                )
+            }
+        }
+    }
+}
+
+impl<T, const ALIGN: usize> Default for AlignedHeap<T, ALIGN> {
+    fn default() -> Self { Self::new() }
+}
+
+unsafe impl<T, const ALIGN: usize> Storage<T> for AlignedHeap<T, ALIGN> {
+    const IS_ALIGNED: bool = true;
+
+    #[inline]
+    fn capacity(&self) -> usize { self.capacity }
+
+    #[inline]
+    fn as_ptr(&self) -> *const T { self.ptr.as_ptr() }
+
+    #[inline]
+    fn as_mut_ptr(&mut self) -> *mut T { self.ptr.as_ptr() }
+
+    #[inline]
+    fn reserve(&mut self, new_capacity: usize) {
+        if self.capacity < new_capacity {
+            let _ = self.reserve_slow(new_capacity, OnFailure::Abort);
+        }
+    }
+
+    #[inline]
+    fn try_reserve(&mut self, new_capacity: usize) -> bool {
+        if self.capacity < new_capacity {
+            self.reserve_slow(new_capacity, OnFailure::Error)
+        } else {
+            true
+        }
+    }
+}
+
+unsafe impl<T, const ALIGN: usize> StorageWithCapacity<T> for AlignedHeap<T, ALIGN> {
+    fn with_capacity(capacity: usize) -> Self { Self::with_capacity(capacity) }
+}
+
+impl<T, const ALIGN: usize> AlignedHeap<T, ALIGN> {
+    /// Try to convert a [`Heap`] storage without reallocating
+    ///
+    /// This only succeeds if `ALIGN` is not larger than the alignment of `T`,
+    /// so the allocation already has the right alignment
+    pub fn try_from_heap(heap: Heap<T>) -> Result<Self, Heap<T>> {
+        if Self::LAYOUT_ALIGN != align_of::<T>() {
+            return Err(heap)
+        }
+
+        let (ptr, capacity) = heap.into_raw_parts();
+
+        // Safety
+        //
+        // the layouts are identical
+        Ok(unsafe { Self::from_raw_parts(ptr, capacity) })
+    }
+
+    /// Try to convert into a [`Heap`] storage without reallocating
+    ///
+    /// This only succeeds if `ALIGN` is not larger than the alignment of `T`,
+    /// so the allocation can be freed with the alignment of `T`
+    pub fn try_into_heap(self) -> Result<Heap<T>, Self> {
+        if Self::LAYOUT_ALIGN != align_of::<T>() {
+            return Err(self)
+        }
+
+        let (ptr, capacity) = self.into_raw_parts();
+
+        // Safety
+        //
+        // the layouts are identical
+        Ok(unsafe { Heap::from_raw_parts(ptr, capacity) })
+    }
+}
@@ -151,11 +151,31 @@ unsafe impl<T, U, A: ?Sized + Allocator> Storage<U> for Heap<T, A> {
             true
         }
     }
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    fn __reserve_exact__(&mut self, new_capacity: usize) {
+        let new_capacity = capacity(new_capacity, size_of::<U>(), size_of::<T>(), Round::Up);
+        if self.capacity < new_capacity {
+            let _ = self.reserve_exact_slow(new_capacity, OnFailure::Abort);
+        }
+    }
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    fn __try_reserve_exact__(&mut self, new_capacity: usize) -> bool {
+        let new_capacity = capacity(new_capacity, size_of::<U>(), size_of::<T>(), Round::Up);
+        if self.capacity < new_capacity {
+            self.reserve_exact_slow(new_capacity, OnFailure::Error)
+        } else {
+            true
+        }
+    }
 }
 
 impl<T, A: Default + Allocator> Heap<T, A> {
     fn with_capacity(capacity: usize) -> Self {
-        if core::mem::size_of::<T>() == 0 {
+        if core::mem::size_of::<T>() == 0 || capacity == 0 {
             return Self::default()
         }
 
@@ -193,6 +213,15 @@ impl<T, A: ?Sized + Allocator> Heap<T, A> {
         let new_capacity = new_capacity
             .max(self.capacity.checked_mul(2).expect("Could not grow further"))
             .max(super::INIT_ALLOC_CAPACITY);
+
+        self.reserve_exact_slow(new_capacity, on_failure)
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn reserve_exact_slow(&mut self, new_capacity: usize, on_failure: OnFailure) -> bool {
+        assert!(new_capacity > self.capacity);
+
         let layout = Layout::new::<T>().repeat(new_capacity).expect("Invalid layout").0;
 
         let ptr = if self.capacity == 0 {
@@ -29,10 +29,16 @@ enum OnFailure {
 
 impl<T> Drop for Heap<T> {
     fn drop(&mut self) {
-        unsafe {
-            let layout = Layout::new::<T>();
-            let layout = Layout::from_size_align_unchecked(layout.size() * self.capacity, layout.align());
-            dealloc(self.ptr.as_ptr().cast(), layout);
+        let size = size_of::<T>().wrapping_mul(self.capacity);
+
+        // zero-sized allocations are never made, so there's nothing to free
+        if size != 0 {
+            unsafe {
+                dealloc(
+                    self.ptr.as_ptr().cast(),
+                    Layout::from_size_align_unchecked(size, align_of::<T>()),
+                )
+            }
         }
     }
 }
@@ -92,6 +98,26 @@ unsafe impl<T, U> Storage<U> for Heap<T> {
             true
         }
     }
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    fn __reserve_exact__(&mut self, new_capacity: usize) {
+        let new_capacity = capacity(new_capacity, size_of::<U>(), size_of::<T>(), Round::Up);
+        if self.capacity < new_capacity {
+            let _ = self.reserve_exact_slow(new_capacity, OnFailure::Abort);
+        }
+    }
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    fn __try_reserve_exact__(&mut self, new_capacity: usize) -> bool {
+        let new_capacity = capacity(new_capacity, size_of::<U>(), size_of::<T>(), Round::Up);
+        if self.capacity < new_capacity {
+            self.reserve_exact_slow(new_capacity, OnFailure::Error)
+        } else {
+            true
+        }
+    }
 }
 
 pub fn padding_needed_for(layout: Layout, align: usize) -> usize {
@@ -135,7 +161,7 @@ pub fn repeat(layout: Layout, n: usize) -> Result<Layout, ()> {
 
 impl<T> Heap<T> {
     fn with_capacity(capacity: usize) -> Self {
-        if core::mem::size_of::<T>() == 0 {
+        if core::mem::size_of::<T>() == 0 || capacity == 0 {
             return Self::new()
         }
 
@@ -171,6 +197,15 @@ impl<T> Heap<T> {
         let new_capacity = new_capacity
             .max(self.capacity.checked_mul(2).expect("Could not grow further"))
             .max(super::INIT_ALLOC_CAPACITY);
+
+        self.reserve_exact_slow(new_capacity, on_failure)
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn reserve_exact_slow(&mut self, new_capacity: usize, on_failure: OnFailure) -> bool {
+        assert!(new_capacity > self.capacity);
+
         let layout = repeat(Layout::new::<T>(), new_capacity).expect("Invalid layout");
 
         let ptr = if self.capacity == 0 {
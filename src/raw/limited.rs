@@ -0,0 +1,419 @@
+use crate::raw::{FixedCapacity, Storage, StorageInit};
+
+use core::mem::size_of;
+#[cfg(target_has_atomic = "ptr")]
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Clone, Copy)]
+enum Limit {
+    Elements(usize),
+    Bytes(usize),
+}
+
+enum OnFailure {
+    Abort,
+    Error,
+}
+
+enum Growth {
+    Amortized,
+    Exact,
+}
+
+/// A storage adapter that refuses to grow past a limit
+///
+/// The limit is either a number of elements ([`Limited::new`]) or a number of
+/// bytes ([`Limited::with_byte_limit`]). `try_reserve` past the limit fails, and
+/// `reserve` past the limit panics.
+///
+/// The wrapped storage is never asked for more than the limit. Storages that can
+/// grow exactly, like [`Heap`](crate::raw::Heap), will never allocate past it, others
+/// may round up their allocation, but the vector will never use more than the limit.
+///
+/// Storages with a [`FixedCapacity`] can't grow, so the limit doesn't apply to them,
+/// and `Limited` keeps their capacity.
+///
+/// ```rust
+/// use generic_vec::{GenericVec, raw::{Heap, Limited}};
+///
+/// let mut vec = GenericVec::with_storage(Limited::with_byte_limit(Heap::<u32>::new(), 40));
+/// vec.extend(0_u32..10);
+///
+/// assert_eq!(vec.capacity(), 10);
+/// assert!(vec.try_push(10).is_err());
+/// ```
+pub struct Limited<S: ?Sized> {
+    limit: Limit,
+    storage: S,
+}
+
+impl<S> Limited<S> {
+    /// Limit the storage to at most `max_capacity` elements
+    pub const fn new(storage: S, max_capacity: usize) -> Self {
+        Self {
+            limit: Limit::Elements(max_capacity),
+            storage,
+        }
+    }
+
+    /// Limit the storage to at most `max_bytes` bytes of elements
+    pub const fn with_byte_limit(storage: S, max_bytes: usize) -> Self {
+        Self {
+            limit: Limit::Bytes(max_bytes),
+            storage,
+        }
+    }
+
+    /// Get the underlying storage
+    pub fn into_inner(self) -> S { self.storage }
+}
+
+impl<S: ?Sized> Limited<S> {
+    /// Get a reference to the underlying storage
+    pub fn get(&self) -> &S { &self.storage }
+
+    /// The largest capacity this storage can grow to, for elements of type `T`
+    pub fn max_capacity<T>(&self) -> usize {
+        match (self.limit, size_of::<T>()) {
+            (Limit::Elements(max_capacity), _) => max_capacity,
+            (Limit::Bytes(_), 0) => usize::MAX,
+            (Limit::Bytes(max_bytes), size) => max_bytes / size,
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn reserve_slow<T>(&mut self, new_capacity: usize, growth: Growth, on_failure: OnFailure) -> bool
+    where
+        S: Storage<T>,
+    {
+        let max_capacity = self.max_capacity::<T>();
+
+        if new_capacity > max_capacity {
+            return match on_failure {
+                OnFailure::Abort => limit_reserve_error(max_capacity, new_capacity),
+                OnFailure::Error => false,
+            }
+        }
+
+        // grow by at least doubling, but never ask the storage for more than the limit
+        let capacity = match growth {
+            Growth::Amortized => new_capacity
+                .max(self.storage.capacity().saturating_mul(2))
+                .min(max_capacity),
+            Growth::Exact => new_capacity,
+        };
+
+        match on_failure {
+            OnFailure::Abort => {
+                self.storage.__reserve_exact__(capacity);
+                true
+            }
+            OnFailure::Error => self.storage.__try_reserve_exact__(capacity),
+        }
+    }
+}
+
+unsafe impl<T, S: ?Sized + StorageInit<T>> StorageInit<T> for Limited<S> {}
+unsafe impl<T, S: ?Sized + Storage<T>> Storage<T> for Limited<S> {
+    #[doc(hidden)]
+    const CONST_CAPACITY: Option<usize> = S::CONST_CAPACITY;
+    const IS_ALIGNED: bool = S::IS_ALIGNED;
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        match S::CONST_CAPACITY {
+            // fixed capacity storages can't grow, so there is nothing to limit
+            Some(_) => self.storage.capacity(),
+            None => self.storage.capacity().min(self.max_capacity::<T>()),
+        }
+    }
+
+    #[inline]
+    fn as_ptr(&self) -> *const T { self.storage.as_ptr() }
+
+    #[inline]
+    fn as_mut_ptr(&mut self) -> *mut T { self.storage.as_mut_ptr() }
+
+    #[inline]
+    fn reserve(&mut self, new_capacity: usize) {
+        if Storage::<T>::capacity(self) < new_capacity {
+            self.reserve_slow(new_capacity, Growth::Amortized, OnFailure::Abort);
+        }
+    }
+
+    #[inline]
+    fn try_reserve(&mut self, new_capacity: usize) -> bool {
+        Storage::<T>::capacity(self) >= new_capacity
+            || self.reserve_slow(new_capacity, Growth::Amortized, OnFailure::Error)
+    }
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    fn __reserve_exact__(&mut self, new_capacity: usize) {
+        if Storage::<T>::capacity(self) < new_capacity {
+            self.reserve_slow(new_capacity, Growth::Exact, OnFailure::Abort);
+        }
+    }
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    fn __try_reserve_exact__(&mut self, new_capacity: usize) -> bool {
+        Storage::<T>::capacity(self) >= new_capacity || self.reserve_slow(new_capacity, Growth::Exact, OnFailure::Error)
+    }
+
+    #[doc(hidden)]
+    #[inline(always)]
+    #[allow(non_snake_case)]
+    unsafe fn __wipe__(&mut self, index: usize, len: usize) { unsafe { self.storage.__wipe__(index, len) } }
+
+    #[doc(hidden)]
+    #[inline(always)]
+    #[allow(non_snake_case)]
+    fn __verify__(&self) { self.storage.__verify__() }
+
+    #[doc(hidden)]
+    #[inline(always)]
+    #[allow(non_snake_case)]
+    unsafe fn __on_drop__(&mut self, len: usize) -> bool { unsafe { self.storage.__on_drop__(len) } }
+}
+
+unsafe impl<T, S: ?Sized + FixedCapacity<T>> FixedCapacity<T> for Limited<S> {
+    const CAPACITY: usize = S::CAPACITY;
+}
+
+/// A memory budget in bytes, that can be shared between [`SharedLimited`] storages
+#[cfg(target_has_atomic = "ptr")]
+#[derive(Debug)]
+pub struct Budget {
+    remaining: AtomicUsize,
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl Budget {
+    /// Create a new budget of `bytes` bytes
+    pub const fn new(bytes: usize) -> Self {
+        Self {
+            remaining: AtomicUsize::new(bytes),
+        }
+    }
+
+    /// The number of bytes left in the budget
+    pub fn remaining(&self) -> usize { self.remaining.load(Ordering::Relaxed) }
+
+    /// Take `bytes` from the budget, returns false if there isn't enough left
+    fn take(&self, bytes: usize) -> bool {
+        self.remaining
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |remaining| {
+                remaining.checked_sub(bytes)
+            })
+            .is_ok()
+    }
+
+    /// Take up to `bytes` from the budget, and return how much was taken
+    fn take_at_most(&self, bytes: usize) -> usize {
+        match self
+            .remaining
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |remaining| {
+                Some(remaining.saturating_sub(bytes))
+            }) {
+            Ok(remaining) | Err(remaining) => remaining.min(bytes),
+        }
+    }
+
+    fn give_back(&self, bytes: usize) { self.remaining.fetch_add(bytes, Ordering::Relaxed); }
+}
+
+/// A storage adapter that draws its capacity from a shared [`Budget`]
+///
+/// Growing the storage takes bytes from the budget, and they are given back when the
+/// storage is dropped. So many vectors can share one memory limit, even across threads.
+///
+/// This is only available on targets with pointer sized atomics.
+/// Like [`Limited`], `try_reserve` fails when the budget is exhausted, and `reserve` panics.
+///
+/// ```rust
+/// use generic_vec::{GenericVec, raw::{Budget, Heap, SharedLimited}};
+///
+/// let budget = Budget::new(64);
+///
+/// let mut a = GenericVec::with_storage(SharedLimited::new(Heap::<u8>::new(), &budget));
+/// let mut b = GenericVec::<u8, _>::with_storage(SharedLimited::new(Heap::<u8>::new(), &budget));
+///
+/// a.extend_from_slice(&[0_u8; 48]);
+/// assert!(!b.try_reserve(32));
+///
+/// drop(a);
+/// assert!(b.try_reserve(32));
+/// ```
+#[cfg(target_has_atomic = "ptr")]
+pub struct SharedLimited<'a, S: ?Sized> {
+    budget: &'a Budget,
+    charged: usize,
+    storage: S,
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<'a, S> SharedLimited<'a, S> {
+    /// Create a new storage that draws from `budget`
+    ///
+    /// The capacity that `storage` already has isn't usable until it's paid for
+    pub const fn new(storage: S, budget: &'a Budget) -> Self {
+        Self {
+            budget,
+            charged: 0,
+            storage,
+        }
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<S: ?Sized> SharedLimited<'_, S> {
+    /// Get a reference to the underlying storage
+    pub fn get(&self) -> &S { &self.storage }
+
+    /// The number of bytes this storage has taken from the budget
+    pub fn charged(&self) -> usize { self.charged }
+
+    #[cold]
+    #[inline(never)]
+    fn reserve_slow<T>(&mut self, new_capacity: usize, growth: Growth, on_failure: OnFailure) -> bool
+    where
+        S: Storage<T>,
+    {
+        let size = size_of::<T>();
+        let old_charged = self.charged;
+        let needed = new_capacity.saturating_mul(size).saturating_sub(old_charged);
+
+        if !self.budget.take(needed) {
+            return match on_failure {
+                OnFailure::Abort => budget_reserve_error(new_capacity, self.budget.remaining()),
+                OnFailure::Error => false,
+            }
+        }
+
+        // charge first, so that the bytes are given back on drop if `reserve` panics
+        self.charged += needed;
+
+        // grow by at least doubling if the budget allows it, but never ask
+        // the storage for more than was paid for
+        if let Growth::Amortized = growth {
+            let doubled = self.storage.capacity().saturating_mul(2).saturating_mul(size);
+            if let Some(extra) = doubled.checked_sub(self.charged) {
+                self.charged += self.budget.take_at_most(extra);
+            }
+        }
+
+        let capacity = match size {
+            0 => new_capacity,
+            size => self.charged / size,
+        };
+
+        match on_failure {
+            OnFailure::Abort => self.storage.__reserve_exact__(capacity),
+            OnFailure::Error => {
+                if !self.storage.__try_reserve_exact__(capacity) {
+                    self.budget.give_back(self.charged - old_charged);
+                    self.charged = old_charged;
+                    return false
+                }
+            }
+        }
+
+        // if the storage allocated more than was asked for, try to pay for
+        // the rest too, so it can be used without touching the budget again
+        let allocated = self.storage.capacity().saturating_mul(size_of::<T>());
+        if let Some(extra) = allocated.checked_sub(self.charged) {
+            self.charged += self.budget.take_at_most(extra);
+        }
+
+        true
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<S: ?Sized> Drop for SharedLimited<'_, S> {
+    fn drop(&mut self) { self.budget.give_back(self.charged) }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+unsafe impl<T, S: ?Sized + StorageInit<T>> StorageInit<T> for SharedLimited<'_, S> {}
+#[cfg(target_has_atomic = "ptr")]
+unsafe impl<T, S: ?Sized + Storage<T>> Storage<T> for SharedLimited<'_, S> {
+    const IS_ALIGNED: bool = S::IS_ALIGNED;
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        match size_of::<T>() {
+            0 => self.storage.capacity(),
+            size => self.storage.capacity().min(self.charged / size),
+        }
+    }
+
+    #[inline]
+    fn as_ptr(&self) -> *const T { self.storage.as_ptr() }
+
+    #[inline]
+    fn as_mut_ptr(&mut self) -> *mut T { self.storage.as_mut_ptr() }
+
+    #[inline]
+    fn reserve(&mut self, new_capacity: usize) {
+        if Storage::<T>::capacity(self) < new_capacity {
+            self.reserve_slow(new_capacity, Growth::Amortized, OnFailure::Abort);
+        }
+    }
+
+    #[inline]
+    fn try_reserve(&mut self, new_capacity: usize) -> bool {
+        Storage::<T>::capacity(self) >= new_capacity
+            || self.reserve_slow(new_capacity, Growth::Amortized, OnFailure::Error)
+    }
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    fn __reserve_exact__(&mut self, new_capacity: usize) {
+        if Storage::<T>::capacity(self) < new_capacity {
+            self.reserve_slow(new_capacity, Growth::Exact, OnFailure::Abort);
+        }
+    }
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    fn __try_reserve_exact__(&mut self, new_capacity: usize) -> bool {
+        Storage::<T>::capacity(self) >= new_capacity || self.reserve_slow(new_capacity, Growth::Exact, OnFailure::Error)
+    }
+
+    #[doc(hidden)]
+    #[inline(always)]
+    #[allow(non_snake_case)]
+    unsafe fn __wipe__(&mut self, index: usize, len: usize) { unsafe { self.storage.__wipe__(index, len) } }
+
+    #[doc(hidden)]
+    #[inline(always)]
+    #[allow(non_snake_case)]
+    fn __verify__(&self) { self.storage.__verify__() }
+
+    #[doc(hidden)]
+    #[inline(always)]
+    #[allow(non_snake_case)]
+    unsafe fn __on_drop__(&mut self, len: usize) -> bool { unsafe { self.storage.__on_drop__(len) } }
+}
+
+#[cold]
+#[inline(never)]
+fn limit_reserve_error(max_capacity: usize, new_capacity: usize) -> ! {
+    panic!(
+        "Tried to reserve {}, but the storage is limited to {} elements",
+        new_capacity, max_capacity
+    )
+}
+
+#[cfg(target_has_atomic = "ptr")]
+#[cold]
+#[inline(never)]
+fn budget_reserve_error(new_capacity: usize, remaining: usize) -> ! {
+    panic!(
+        "Tried to reserve {}, but only {} bytes are left in the budget",
+        new_capacity, remaining
+    )
+}
@@ -0,0 +1,313 @@
+use crate::raw::Storage;
+
+use core::{
+    convert::TryFrom,
+    marker::PhantomData,
+    mem::{align_of, size_of},
+    ops::Range,
+    ptr::NonNull,
+};
+use std::{
+    fs::{File, OpenOptions},
+    io,
+    os::unix::io::AsRawFd,
+    path::Path,
+};
+
+const INIT_ALLOC_CAPACITY: usize = 4;
+
+const MAGIC: [u8; 8] = *b"GVECMMAP";
+
+/// The number of bytes reserved for the header at the start of the file,
+/// the elements start directly after the header
+const HEADER_SIZE: usize = 64;
+
+#[repr(C)]
+struct Header {
+    magic: [u8; 8],
+    elem_size: u64,
+    elem_align: u64,
+    len: u64,
+}
+
+/// A storage that keeps its elements in a memory mapped file
+///
+/// The file starts with a small header that records the element layout and the
+/// length of the vector, followed by the elements themselves. Growing the storage
+/// extends the file and remaps it, so the contents are never copied by hand.
+///
+/// Only [`Copy`] types can be stored, because the elements are written to disk
+/// as raw bytes and are never dropped.
+///
+/// The length in the header is updated by [`MmapStorage::set_stored_len`], which is
+/// called by [`MmapVec::flush`](crate::MmapVec::flush), [`MmapVec::sync_range`](crate::MmapVec::sync_range),
+/// and when the vector is dropped. The OS writes the mapping back to the file
+/// lazily, so flush the vector to make sure the elements are on disk.
+///
+/// Usable with the `mmap` feature on Linux
+pub struct MmapStorage<T: Copy> {
+    file: File,
+    ptr: NonNull<u8>,
+    map_len: usize,
+    capacity: usize,
+    mark: PhantomData<T>,
+}
+
+unsafe impl<T: Copy + Send> Send for MmapStorage<T> {}
+unsafe impl<T: Copy + Sync> Sync for MmapStorage<T> {}
+
+enum OnFailure {
+    Abort,
+    Error,
+}
+
+impl<T: Copy> Drop for MmapStorage<T> {
+    fn drop(&mut self) {
+        unsafe {
+            libc::munmap(self.ptr.as_ptr().cast(), self.map_len);
+        }
+    }
+}
+
+/// The smallest page size on any supported platform, mappings are always
+/// aligned to at least this much
+pub(in crate::raw) const MIN_PAGE_SIZE: usize = 4096;
+
+pub(in crate::raw) fn page_size() -> usize { unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize } }
+
+/// Round `size` up to a whole number of pages
+pub(in crate::raw) fn round_to_page(size: usize) -> Option<usize> {
+    let page_size = page_size();
+    Some(size.checked_add(page_size - 1)? & !(page_size - 1))
+}
+
+fn map(file: &File, len: usize) -> io::Result<NonNull<u8>> {
+    let ptr = unsafe {
+        libc::mmap(
+            core::ptr::null_mut(),
+            len,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_SHARED,
+            file.as_raw_fd(),
+            0,
+        )
+    };
+
+    if ptr == libc::MAP_FAILED {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(NonNull::new(ptr.cast()).expect("mmap returned a null pointer"))
+    }
+}
+
+fn invalid_data(msg: &'static str) -> io::Error { io::Error::new(io::ErrorKind::InvalidData, msg) }
+
+impl<T: Copy> MmapStorage<T> {
+    fn capacity_for(map_len: usize) -> usize {
+        match size_of::<T>() {
+            0 => usize::MAX,
+            size => (map_len - HEADER_SIZE) / size,
+        }
+    }
+
+    fn byte_len(capacity: usize) -> Option<usize> { capacity.checked_mul(size_of::<T>())?.checked_add(HEADER_SIZE) }
+
+    fn header(&self) -> *mut Header { self.ptr.as_ptr().cast() }
+
+    fn from_file(file: File, map_len: usize) -> io::Result<Self> {
+        let ptr = map(&file, map_len)?;
+        Ok(Self {
+            file,
+            ptr,
+            map_len,
+            capacity: Self::capacity_for(map_len),
+            mark: PhantomData,
+        })
+    }
+
+    /// Create a new, empty storage at `path`, replacing the file if it already exists
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified, truncated or mapped by anything else, including
+    /// another `MmapStorage` of the same file, while this storage is alive
+    pub unsafe fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        file.set_len(HEADER_SIZE as u64)?;
+
+        let storage = Self::from_file(file, HEADER_SIZE)?;
+
+        unsafe {
+            storage.header().write(Header {
+                magic: MAGIC,
+                elem_size: size_of::<T>() as u64,
+                elem_align: align_of::<T>() as u64,
+                len: 0,
+            })
+        }
+
+        Ok(storage)
+    }
+
+    /// Open a storage that was previously created by [`MmapStorage::create`]
+    ///
+    /// Returns the storage and the length that was stored in the header
+    ///
+    /// # Safety
+    ///
+    /// * The first `len` elements in the file must be valid `T`s
+    /// * The file must not be modified, truncated or mapped by anything else, including
+    ///   another `MmapStorage` of the same file, while this storage is alive
+    pub unsafe fn open<P: AsRef<Path>>(path: P) -> io::Result<(Self, usize)> {
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+        let map_len = file.metadata()?.len();
+        let map_len = usize::try_from(map_len).map_err(|_| invalid_data("file is too large to map"))?;
+
+        if map_len < HEADER_SIZE {
+            return Err(invalid_data("file is too small to contain a header"))
+        }
+
+        let storage = Self::from_file(file, map_len)?;
+        let header = unsafe { storage.header().read() };
+
+        if header.magic != MAGIC {
+            return Err(invalid_data("file is not a mmap storage"))
+        }
+
+        if header.elem_size != size_of::<T>() as u64 || header.elem_align != align_of::<T>() as u64 {
+            return Err(invalid_data("file was created with a different element type"))
+        }
+
+        match usize::try_from(header.len) {
+            Ok(len) if len <= storage.capacity => Ok((storage, len)),
+            _ => Err(invalid_data("stored length is larger than the file")),
+        }
+    }
+
+    /// The length that is currently stored in the header
+    pub fn stored_len(&self) -> usize { unsafe { (*self.header()).len as usize } }
+
+    /// Write `len` to the header, this will be persisted the next time the header is synced
+    pub fn set_stored_len(&mut self, len: usize) { unsafe { (*self.header()).len = len as u64 } }
+
+    /// Synchronously write the entire mapping, including the header, back to the file
+    pub fn flush(&self) -> io::Result<()> { self.msync(0, self.map_len) }
+
+    /// Synchronously write the elements in `range`, and the header, back to the file
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds of the capacity
+    pub fn sync_range(&self, range: Range<usize>) -> io::Result<()> {
+        assert!(
+            range.start <= range.end && range.end <= self.capacity,
+            "Tried to sync {:?}, but the capacity is {}",
+            range,
+            self.capacity
+        );
+
+        self.msync(0, HEADER_SIZE)?;
+
+        let size = size_of::<T>();
+        if size == 0 || range.start == range.end {
+            return Ok(())
+        }
+
+        self.msync(HEADER_SIZE + range.start * size, (range.end - range.start) * size)
+    }
+
+    fn msync(&self, offset: usize, len: usize) -> io::Result<()> {
+        // `msync` requires a page aligned address
+        let aligned = offset - offset % page_size();
+        let len = len + (offset - aligned);
+
+        let ret = unsafe { libc::msync(self.ptr.as_ptr().add(aligned).cast(), len, libc::MS_SYNC) };
+
+        if ret == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn reserve_slow(&mut self, new_capacity: usize, on_failure: OnFailure) -> bool {
+        assert!(new_capacity > self.capacity);
+
+        // grow by at least doubling
+        let new_capacity = new_capacity
+            .max(self.capacity.saturating_mul(2))
+            .max(INIT_ALLOC_CAPACITY);
+
+        match (self.grow(new_capacity), on_failure) {
+            (Ok(()), _) => true,
+            (Err(err), OnFailure::Abort) => grow_error(new_capacity, err),
+            (Err(_), OnFailure::Error) => false,
+        }
+    }
+
+    fn grow(&mut self, new_capacity: usize) -> io::Result<()> {
+        let map_len = Self::byte_len(new_capacity).ok_or_else(|| invalid_data("capacity overflow"))?;
+
+        self.file.set_len(map_len as u64)?;
+
+        let ptr = unsafe { libc::mremap(self.ptr.as_ptr().cast(), self.map_len, map_len, libc::MREMAP_MAYMOVE) };
+
+        if ptr == libc::MAP_FAILED {
+            let err = io::Error::last_os_error();
+            // the old mapping is still intact, so shrink the file back to match it
+            let _ = self.file.set_len(self.map_len as u64);
+            return Err(err)
+        }
+
+        self.ptr = NonNull::new(ptr.cast()).expect("mremap returned a null pointer");
+        self.map_len = map_len;
+        self.capacity = new_capacity;
+
+        Ok(())
+    }
+}
+
+#[cold]
+#[inline(never)]
+fn grow_error(new_capacity: usize, err: io::Error) -> ! {
+    panic!("Could not grow the mmap storage to {} elements: {}", new_capacity, err)
+}
+
+unsafe impl<T: Copy> Storage<T> for MmapStorage<T> {
+    const IS_ALIGNED: bool = align_of::<T>() <= HEADER_SIZE;
+
+    fn capacity(&self) -> usize { self.capacity }
+
+    fn as_ptr(&self) -> *const T { unsafe { self.ptr.as_ptr().add(HEADER_SIZE).cast() } }
+
+    fn as_mut_ptr(&mut self) -> *mut T { unsafe { self.ptr.as_ptr().add(HEADER_SIZE).cast() } }
+
+    fn reserve(&mut self, new_capacity: usize) {
+        if self.capacity < new_capacity {
+            let _ = self.reserve_slow(new_capacity, OnFailure::Abort);
+        }
+    }
+
+    fn try_reserve(&mut self, new_capacity: usize) -> bool {
+        if self.capacity < new_capacity {
+            self.reserve_slow(new_capacity, OnFailure::Error)
+        } else {
+            true
+        }
+    }
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    unsafe fn __on_drop__(&mut self, len: usize) -> bool {
+        self.set_stored_len(len);
+        // the elements stay in the file
+        true
+    }
+}
@@ -0,0 +1,260 @@
+use crate::raw::{capacity::fixed_capacity_reserve_error, Storage};
+
+use core::{
+    cell::Cell,
+    marker::PhantomData,
+    mem::{align_of, size_of, MaybeUninit},
+    ptr::NonNull,
+};
+
+const END: usize = usize::MAX;
+
+/// A single block in a [`Pool`]
+///
+/// This is only used as the backing memory of a [`Pool`],
+/// so a slab of blocks can be put on the stack, or in a `static`
+///
+/// ```rust
+/// use generic_vec::raw::{Pool, PoolSlot};
+///
+/// let mut slab = [PoolSlot::<u8, 1500>::NEW; 16];
+///
+/// let pool = Pool::new(&mut slab);
+/// assert_eq!(pool.available(), 16);
+/// ```
+#[repr(C)]
+pub struct PoolSlot<T, const BLOCK: usize> {
+    next: usize,
+    block: MaybeUninit<[T; BLOCK]>,
+}
+
+impl<T, const BLOCK: usize> PoolSlot<T, BLOCK> {
+    /// An unused slot
+    pub const NEW: Self = Self {
+        next: END,
+        block: MaybeUninit::uninit(),
+    };
+}
+
+/// The parts of a [`Pool`] that don't depend on the size of a block
+struct RawPool<T> {
+    slots: NonNull<u8>,
+    stride: usize,
+    block: usize,
+    head: Cell<usize>,
+    available: Cell<usize>,
+    mark: PhantomData<T>,
+}
+
+/// A pool of fixed size blocks, each of which can hold up to `BLOCK` elements
+///
+/// Each [`PoolBlock`] storage takes a block from the pool the first time it reserves
+/// any space, and returns it to the pool when it's dropped. Because every block has the
+/// same size, the blocks can be reused without any fragmentation.
+///
+/// ```rust
+/// use generic_vec::{GenericVec, raw::{Pool, PoolSlot}};
+///
+/// let mut slab = [PoolSlot::<u8, 4>::NEW; 2];
+///
+/// let pool = Pool::new(&mut slab);
+///
+/// let mut a = GenericVec::with_storage(pool.block());
+/// let mut b = GenericVec::with_storage(pool.block());
+/// let mut c = GenericVec::with_storage(pool.block());
+///
+/// a.push(0);
+/// b.push(1);
+///
+/// // the pool is exhausted
+/// assert!(!c.try_reserve(1));
+///
+/// // but it can be reused after a block is returned
+/// drop(a);
+/// c.push(2);
+/// ```
+pub struct Pool<'a, T, const BLOCK: usize> {
+    raw: RawPool<T>,
+    slots: PhantomData<&'a mut [PoolSlot<T, BLOCK>]>,
+}
+
+/// A storage that holds a single block from a [`Pool`]
+///
+/// The block is taken on the first reserve, and is returned to the pool on drop.
+/// If the pool is exhausted, `try_reserve` will fail and `reserve` will panic. Reserving
+/// more than the pool's block size always fails.
+pub struct PoolBlock<'pool, T> {
+    pool: &'pool RawPool<T>,
+    slot: usize,
+}
+
+impl<'a, T, const BLOCK: usize> Pool<'a, T, BLOCK> {
+    /// Create a new pool that hands out the given slots
+    pub fn new(slots: &'a mut [PoolSlot<T, BLOCK>]) -> Self {
+        for (i, slot) in slots.iter_mut().enumerate() {
+            slot.next = i + 1;
+        }
+
+        if let Some(last) = slots.last_mut() {
+            last.next = END;
+        }
+
+        Self {
+            raw: RawPool {
+                head: Cell::new(if slots.is_empty() { END } else { 0 }),
+                available: Cell::new(slots.len()),
+                slots: NonNull::from(slots).cast(),
+                stride: size_of::<PoolSlot<T, BLOCK>>(),
+                block: BLOCK,
+                mark: PhantomData,
+            },
+            slots: PhantomData,
+        }
+    }
+
+    /// The number of blocks that haven't been taken
+    pub fn available(&self) -> usize { self.raw.available.get() }
+
+    /// Create a new, empty, storage that will take a block from this pool when needed
+    pub fn block(&self) -> PoolBlock<'_, T> {
+        PoolBlock {
+            pool: &self.raw,
+            slot: END,
+        }
+    }
+}
+
+impl<T> RawPool<T> {
+    /// A pointer to the slot at `index`
+    ///
+    /// # Safety
+    ///
+    /// `index` must be in bounds of the slab
+    unsafe fn slot(&self, index: usize) -> *mut u8 { unsafe { self.slots.as_ptr().add(index * self.stride) } }
+
+    fn take(&self) -> Option<usize> {
+        let index = self.head.get();
+
+        if index == END {
+            return None
+        }
+
+        // Safety
+        //
+        // * `head` always points to a free slot in the slab
+        // * `PoolSlot` is `repr(C)`, so `next` is the first field
+        unsafe {
+            self.head.set(self.slot(index).cast::<usize>().read());
+        }
+        self.available.set(self.available.get() - 1);
+
+        Some(index)
+    }
+
+    /// Return the block at `index` to the pool
+    ///
+    /// # Safety
+    ///
+    /// `index` must have been taken from this pool, and not returned yet
+    unsafe fn give(&self, index: usize) {
+        unsafe {
+            self.slot(index).cast::<usize>().write(self.head.get());
+        }
+        self.head.set(index);
+        self.available.set(self.available.get() + 1);
+    }
+}
+
+impl<T> PoolBlock<'_, T> {
+    #[cold]
+    #[inline(never)]
+    fn take(&mut self, new_capacity: usize) -> bool {
+        if new_capacity > self.pool.block {
+            return false
+        }
+
+        match self.pool.take() {
+            Some(slot) => {
+                self.slot = slot;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cold]
+#[inline(never)]
+fn pool_exhausted() -> ! { panic!("Tried to take a block from an exhausted pool") }
+
+impl<T> Drop for PoolBlock<'_, T> {
+    fn drop(&mut self) {
+        if self.slot != END {
+            // Safety
+            //
+            // the slot was taken from this pool, and is only returned here
+            unsafe { self.pool.give(self.slot) }
+        }
+    }
+}
+
+unsafe impl<T> Storage<T> for PoolBlock<'_, T> {
+    const IS_ALIGNED: bool = true;
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        if size_of::<T>() == 0 {
+            usize::MAX
+        } else if self.slot == END {
+            0
+        } else {
+            self.pool.block
+        }
+    }
+
+    #[inline]
+    fn as_ptr(&self) -> *const T {
+        if self.slot == END {
+            NonNull::dangling().as_ptr()
+        } else {
+            // Safety
+            //
+            // * the slot was taken from this pool, so it's in bounds of the slab
+            // * `PoolSlot` is `repr(C)`, so the block is right after `next`
+            //   (padded to the alignment of `T`)
+            unsafe { first_elem(self.pool.slot(self.slot)) }
+        }
+    }
+
+    #[inline]
+    fn as_mut_ptr(&mut self) -> *mut T { self.as_ptr() as *mut T }
+
+    #[inline]
+    fn reserve(&mut self, new_capacity: usize) {
+        if self.capacity() < new_capacity && !self.take(new_capacity) {
+            if new_capacity > self.pool.block {
+                fixed_capacity_reserve_error(self.pool.block, new_capacity)
+            } else {
+                pool_exhausted()
+            }
+        }
+    }
+
+    #[inline]
+    fn try_reserve(&mut self, new_capacity: usize) -> bool {
+        self.capacity() >= new_capacity || self.take(new_capacity)
+    }
+}
+
+/// Get a pointer to the block in the slot at `slot`
+///
+/// # Safety
+///
+/// `slot` must point to a `PoolSlot<T, _>`
+unsafe fn first_elem<T>(slot: *mut u8) -> *const T {
+    // `PoolSlot` is `repr(C)`, so the block starts right after `next`, rounded up
+    // to the alignment of `T`. This doesn't depend on the number of elements in the block
+    let align = align_of::<T>();
+    let offset = (size_of::<usize>() + align - 1) & !(align - 1);
+    unsafe { slot.add(offset).cast() }
+}
@@ -0,0 +1,322 @@
+use crate::{
+    raw::{
+        mmap::{round_to_page, MIN_PAGE_SIZE},
+        Storage,
+    },
+    GenericVec,
+};
+
+use core::{
+    cell::Cell,
+    marker::PhantomData,
+    mem::{align_of, size_of},
+    ptr::NonNull,
+};
+use std::io;
+
+const INIT_ALLOC_CAPACITY: usize = 4;
+
+/// A storage that reserves a range of virtual memory up front, and commits
+/// it as the storage grows
+///
+/// Because the whole range is reserved when the storage is created, growing
+/// the storage never moves the elements. So pointers to the elements stay
+/// valid for as long as the elements are in the vector.
+///
+/// Reserving address space is cheap, only the committed pages use memory, so
+/// it's fine to reserve far more than you expect to use. Growing past the
+/// reserved capacity fails, like a fixed capacity storage.
+///
+/// ```rust
+/// use generic_vec::{GenericVec, raw::Reserved};
+///
+/// let mut vec = GenericVec::with_storage(Reserved::new(1 << 30)?);
+/// vec.push(0_u64);
+/// let first = vec.as_ptr();
+///
+/// vec.extend(1..100_000);
+/// assert_eq!(vec.as_ptr(), first);
+/// # Ok::<_, std::io::Error>(())
+/// ```
+///
+/// Usable with the `mmap` feature on Linux
+pub struct Reserved<T> {
+    ptr: NonNull<u8>,
+    reserved: usize,
+    committed: usize,
+    mark: PhantomData<T>,
+}
+
+unsafe impl<T: Send> Send for Reserved<T> {}
+unsafe impl<T: Sync> Sync for Reserved<T> {}
+
+enum OnFailure {
+    Abort,
+    Error,
+}
+
+impl<T> Drop for Reserved<T> {
+    fn drop(&mut self) {
+        if self.reserved != 0 {
+            unsafe {
+                libc::munmap(self.ptr.as_ptr().cast(), self.reserved);
+            }
+        }
+    }
+}
+
+impl<T> Reserved<T> {
+    /// Reserve enough address space for `max_capacity` elements
+    ///
+    /// None of the memory is committed until the storage is reserved
+    pub fn new(max_capacity: usize) -> io::Result<Self> {
+        let reserved = match max_capacity.checked_mul(size_of::<T>()) {
+            Some(size) => round_to_page(size).ok_or_else(capacity_overflow)?,
+            None => return Err(capacity_overflow()),
+        };
+
+        if reserved == 0 {
+            return Ok(Self {
+                ptr: NonNull::<T>::dangling().cast(),
+                reserved: 0,
+                committed: 0,
+                mark: PhantomData,
+            })
+        }
+
+        let ptr = unsafe {
+            libc::mmap(
+                core::ptr::null_mut(),
+                reserved,
+                libc::PROT_NONE,
+                libc::MAP_PRIVATE | libc::MAP_ANONYMOUS | libc::MAP_NORESERVE,
+                -1,
+                0,
+            )
+        };
+
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error())
+        }
+
+        Ok(Self {
+            ptr: NonNull::new(ptr.cast()).expect("mmap returned a null pointer"),
+            reserved,
+            committed: 0,
+            mark: PhantomData,
+        })
+    }
+
+    /// The largest capacity this storage can grow to
+    pub fn max_capacity(&self) -> usize {
+        match size_of::<T>() {
+            0 => usize::MAX,
+            size => self.reserved / size,
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn reserve_slow(&mut self, new_capacity: usize, on_failure: OnFailure) -> bool {
+        let max_capacity = self.max_capacity();
+
+        if new_capacity > max_capacity {
+            return match on_failure {
+                OnFailure::Abort => reservation_exhausted(max_capacity, new_capacity),
+                OnFailure::Error => false,
+            }
+        }
+
+        // grow by at least doubling, but never past the reservation
+        let new_capacity = new_capacity
+            .max(Storage::<T>::capacity(self).saturating_mul(2))
+            .max(INIT_ALLOC_CAPACITY)
+            .min(max_capacity);
+
+        // commit whole pages, the reservation is always a whole number of pages
+        let committed =
+            round_to_page(new_capacity * size_of::<T>()).map_or(self.reserved, |size| size.min(self.reserved));
+
+        let ret = unsafe {
+            libc::mprotect(
+                self.ptr.as_ptr().add(self.committed).cast(),
+                committed - self.committed,
+                libc::PROT_READ | libc::PROT_WRITE,
+            )
+        };
+
+        match (ret, on_failure) {
+            (0, _) => {
+                self.committed = committed;
+                true
+            }
+            (_, OnFailure::Abort) => commit_error(new_capacity, io::Error::last_os_error()),
+            (_, OnFailure::Error) => false,
+        }
+    }
+}
+
+fn capacity_overflow() -> io::Error { io::Error::new(io::ErrorKind::InvalidInput, "capacity overflow") }
+
+#[cold]
+#[inline(never)]
+fn reservation_exhausted(max_capacity: usize, new_capacity: usize) -> ! {
+    panic!(
+        "Tried to reserve {}, but only reserved address space for {} elements",
+        new_capacity, max_capacity
+    )
+}
+
+#[cold]
+#[inline(never)]
+fn commit_error(new_capacity: usize, err: io::Error) -> ! {
+    panic!("Could not commit memory for {} elements: {}", new_capacity, err)
+}
+
+unsafe impl<T> Storage<T> for Reserved<T> {
+    const IS_ALIGNED: bool = align_of::<T>() <= MIN_PAGE_SIZE;
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        match size_of::<T>() {
+            0 => usize::MAX,
+            size => self.committed / size,
+        }
+    }
+
+    #[inline]
+    fn as_ptr(&self) -> *const T { self.ptr.as_ptr().cast() }
+
+    #[inline]
+    fn as_mut_ptr(&mut self) -> *mut T { self.ptr.as_ptr().cast() }
+
+    #[inline]
+    fn reserve(&mut self, new_capacity: usize) {
+        if Storage::<T>::capacity(self) < new_capacity {
+            let _ = self.reserve_slow(new_capacity, OnFailure::Abort);
+        }
+    }
+
+    #[inline]
+    fn try_reserve(&mut self, new_capacity: usize) -> bool {
+        Storage::<T>::capacity(self) >= new_capacity || self.reserve_slow(new_capacity, OnFailure::Error)
+    }
+}
+
+/// An append-only handle to a vector backed by a [`Reserved`] storage
+///
+/// This struct is created by [`GenericVec::appender`].
+/// See its documentation for more.
+pub struct Appender<'a, T> {
+    vec: *mut GenericVec<T, Reserved<T>>,
+    len: Cell<usize>,
+    mark: PhantomData<&'a mut GenericVec<T, Reserved<T>>>,
+}
+
+impl<T> GenericVec<T, Reserved<T>> {
+    /// Create an append-only handle to this vector
+    ///
+    /// Elements can be pushed through a shared reference to the [`Appender`], and
+    /// each push returns a reference to the new element that lives as long as the
+    /// borrow of the vector. This is sound because elements in a [`Reserved`] storage
+    /// never move, and an `Appender` can't remove or modify existing elements.
+    ///
+    /// ```rust
+    /// use generic_vec::{GenericVec, raw::Reserved};
+    ///
+    /// let mut vec = GenericVec::with_storage(Reserved::new(1 << 20)?);
+    ///
+    /// let appender = vec.appender();
+    /// let hello = appender.push(String::from("hello"));
+    /// let world = appender.push(String::from("world"));
+    ///
+    /// // `hello` is still valid, even though the vector grew
+    /// assert_eq!(hello, "hello");
+    /// assert_eq!(world, "world");
+    ///
+    /// drop(appender);
+    /// assert_eq!(vec, ["hello", "world"]);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn appender(&mut self) -> Appender<'_, T> {
+        Appender {
+            len: Cell::new(self.len()),
+            vec: self,
+            mark: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Appender<'a, T> {
+    /// The number of elements in the vector
+    pub fn len(&self) -> usize { self.len.get() }
+
+    /// Returns true if the vector contains no elements
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Appends an element to the back of the vector, and returns a reference to it
+    ///
+    /// # Panic
+    ///
+    /// Panics if the reserved address space is exhausted, or if the memory
+    /// could not be committed
+    pub fn push(&self, value: T) -> &'a T {
+        let len = self.len();
+
+        // Safety
+        //
+        // * the vector is mutably borrowed for `'a`, and only accessed through this `Appender`
+        // * reserving never moves existing elements, so references returned by
+        //   earlier pushes stay valid
+        unsafe {
+            (*self.vec).storage.reserve(len.saturating_add(1));
+            self.push_unchecked(value)
+        }
+    }
+
+    /// Appends an element to the back of the vector, and returns a reference to it
+    ///
+    /// Returns `Err(value)` if the storage can't grow
+    pub fn try_push(&self, value: T) -> Result<&'a T, T> {
+        let len = self.len();
+
+        // Safety
+        //
+        // * the vector is mutably borrowed for `'a`, and only accessed through this `Appender`
+        // * reserving never moves existing elements, so references returned by
+        //   earlier pushes stay valid
+        unsafe {
+            if (*self.vec).storage.try_reserve(len.saturating_add(1)) {
+                Ok(self.push_unchecked(value))
+            } else {
+                Err(value)
+            }
+        }
+    }
+
+    /// # Safety
+    ///
+    /// the storage must have space for at least one more element
+    unsafe fn push_unchecked(&self, value: T) -> &'a T {
+        let len = self.len();
+
+        // Safety
+        //
+        // the element at `len` is past the end of all the references handed out so far
+        unsafe {
+            let ptr = (*self.vec).storage.as_mut_ptr().add(len);
+            ptr.write(value);
+            self.len.set(len + 1);
+            &*ptr
+        }
+    }
+}
+
+impl<T> Drop for Appender<'_, T> {
+    fn drop(&mut self) {
+        // Safety
+        //
+        // all elements up to `len` were initialized by `push`
+        unsafe { (*self.vec).set_len_unchecked(self.len.get()) }
+    }
+}
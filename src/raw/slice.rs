@@ -81,3 +81,27 @@ unsafe impl<T: Copy> Storage<T> for [T] {
 
     fn try_reserve(&mut self, capacity: usize) -> bool { capacity <= self.capacity() }
 }
+
+unsafe impl<T> Storage<T> for [MaybeUninit<T>] {
+    const IS_ALIGNED: bool = true;
+
+    fn capacity(&self) -> usize {
+        if size_of::<T>() == 0 {
+            usize::MAX
+        } else {
+            self.len()
+        }
+    }
+
+    fn as_ptr(&self) -> *const T { self.as_ptr().cast() }
+
+    fn as_mut_ptr(&mut self) -> *mut T { self.as_mut_ptr().cast() }
+
+    fn reserve(&mut self, capacity: usize) {
+        if capacity > Storage::<T>::capacity(self) {
+            fixed_capacity_reserve_error(self.len(), capacity)
+        }
+    }
+
+    fn try_reserve(&mut self, capacity: usize) -> bool { capacity <= Storage::<T>::capacity(self) }
+}
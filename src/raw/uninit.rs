@@ -3,7 +3,7 @@
 
 use core::mem::{align_of, size_of, MaybeUninit};
 
-use super::{Storage, StorageWithCapacity};
+use super::{FixedCapacity, Storage, StorageWithCapacity};
 
 #[repr(C)]
 struct AlignedBuffer<T, A> {
@@ -125,3 +125,7 @@ unsafe impl<U, T, A> Storage<U> for UninitBuffer<T, A> {
 
     fn try_reserve(&mut self, capacity: usize) -> bool { capacity <= size::<U, T, A>() }
 }
+
+unsafe impl<U, T, A> FixedCapacity<U> for UninitBuffer<T, A> {
+    const CAPACITY: usize = size::<U, T, A>();
+}
@@ -0,0 +1,84 @@
+use crate::raw::Storage;
+
+use std::vec::Vec;
+
+/// A storage that borrows the allocation of a [`Vec`]
+///
+/// While the storage is alive, the `Vec` has a length of zero, and the elements
+/// are owned by the [`GenericVec`](crate::GenericVec) using this storage. Growing
+/// the storage uses [`Vec::reserve`], so the `Vec` keeps its allocation.
+///
+/// When the [`GenericVec`](crate::GenericVec) is dropped, its elements are given
+/// back to the `Vec`. The elements can also be given back early with [`VecStorage::into_vec`].
+///
+/// Usable with the `alloc` feature
+pub struct VecStorage<'a, T> {
+    vec: &'a mut Vec<T>,
+}
+
+impl<'a, T> VecStorage<'a, T> {
+    /// Take ownership of the elements in `vec`
+    ///
+    /// Returns the number of elements that were in `vec` and the storage,
+    /// this pair can be passed to [`GenericVec::from_raw_parts`](crate::GenericVec::from_raw_parts)
+    pub fn new(vec: &'a mut Vec<T>) -> (usize, Self) {
+        let len = vec.len();
+
+        // Safety
+        //
+        // zero is always a valid length, the elements are now owned
+        // by the caller
+        unsafe { vec.set_len(0) }
+
+        (len, Self { vec })
+    }
+
+    /// Give the first `len` elements back to the `Vec`
+    ///
+    /// # Safety
+    ///
+    /// the first `len` elements of this storage must be initialized,
+    /// and must not be used after this call
+    pub unsafe fn into_vec(self, len: usize) -> &'a mut Vec<T> {
+        unsafe { self.vec.set_len(len) }
+        self.vec
+    }
+}
+
+unsafe impl<T> Storage<T> for VecStorage<'_, T> {
+    const IS_ALIGNED: bool = true;
+
+    #[inline]
+    fn capacity(&self) -> usize { self.vec.capacity() }
+
+    #[inline]
+    fn as_ptr(&self) -> *const T { self.vec.as_ptr() }
+
+    #[inline]
+    fn as_mut_ptr(&mut self) -> *mut T { self.vec.as_mut_ptr() }
+
+    #[inline]
+    fn reserve(&mut self, new_capacity: usize) {
+        // the `Vec` is always empty, so reserving `new_capacity`
+        // more elements gives a capacity of at least `new_capacity`
+        if self.vec.capacity() < new_capacity {
+            self.vec.reserve(new_capacity)
+        }
+    }
+
+    #[inline]
+    fn try_reserve(&mut self, new_capacity: usize) -> bool {
+        self.vec.capacity() >= new_capacity || self.vec.try_reserve(new_capacity).is_ok()
+    }
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    unsafe fn __on_drop__(&mut self, len: usize) -> bool {
+        // Safety
+        //
+        // the first `len` elements are initialized, and the vector
+        // won't use them after this call
+        unsafe { self.vec.set_len(len) }
+        true
+    }
+}
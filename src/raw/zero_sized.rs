@@ -1,4 +1,4 @@
-use crate::raw::{Storage, StorageWithCapacity};
+use crate::raw::{FixedCapacity, Storage, StorageWithCapacity};
 use core::marker::PhantomData;
 
 /// A storage that can hold zero sized types
@@ -59,6 +59,10 @@ unsafe impl<T> Storage<T> for ZeroSized<T> {
     fn capacity(&self) -> usize { usize::MAX }
 }
 
+unsafe impl<T> FixedCapacity<T> for ZeroSized<T> {
+    const CAPACITY: usize = usize::MAX;
+}
+
 unsafe impl<T> StorageWithCapacity<T> for ZeroSized<T> {
     #[inline]
     fn with_capacity(_: usize) -> Self { Self::NEW }
@@ -0,0 +1,210 @@
+use crate::raw::{FixedCapacity, Storage, StorageWithCapacity};
+
+use core::mem::{size_of, ManuallyDrop, MaybeUninit};
+use zeroize::Zeroize;
+
+/// A storage adapter that wipes memory that is no longer in use
+///
+/// * on drop, the entire capacity is overwritten with zeroes
+/// * when elements are removed from a [`GenericVec`](crate::GenericVec) (with `truncate`,
+///   `pop`, `remove`, `drain`, etc.), their memory is overwritten with zeroes
+/// * when the storage needs to grow, a new storage is allocated, the old contents are copied
+///   over, and the old storage is overwritten with zeroes before it's freed. This means
+///   that `Zeroizing` will never `realloc`.
+///
+/// Growing requires creating a new storage, so `Zeroizing` can only be used with storages that
+/// implement [`StorageWithCapacity`]. `Zeroizing` also needs to be able to view the storage as bytes,
+/// so the storage must implement `Storage<u8>`.
+///
+/// Usable with the `zeroize` feature
+///
+/// ```rust
+/// use generic_vec::{GenericVec, raw::{Heap, Zeroizing}};
+///
+/// let mut vec = GenericVec::with_storage(Zeroizing::new(Heap::<u8>::new()));
+/// vec.extend_from_slice(b"hunter2");
+/// vec.truncate(6);
+///
+/// // the removed byte was wiped
+/// assert_eq!(unsafe { *vec.as_ptr().add(6) }, 0);
+/// ```
+#[repr(transparent)]
+pub struct Zeroizing<S: ?Sized + Storage<u8>>(S);
+
+enum OnFailure {
+    Abort,
+    Error,
+}
+
+enum Growth {
+    Amortized,
+    Exact,
+}
+
+impl<S: ?Sized + Storage<u8>> Drop for Zeroizing<S> {
+    fn drop(&mut self) {
+        let len = self.0.capacity();
+        unsafe { wipe(self.0.as_mut_ptr(), len) }
+    }
+}
+
+/// Overwrite `len` bytes starting at `ptr` with zeroes, in a way that won't be optimized out
+///
+/// # Safety
+///
+/// `ptr` must be valid for writes of `len` bytes
+pub(crate) unsafe fn wipe(ptr: *mut u8, len: usize) {
+    unsafe { core::slice::from_raw_parts_mut(ptr.cast::<MaybeUninit<u8>>(), len).zeroize() }
+}
+
+impl<S: Storage<u8>> Zeroizing<S> {
+    /// Create a new `Zeroizing` storage, wrapping the given storage
+    pub const fn new(storage: S) -> Self { Self(storage) }
+
+    /// Get the underlying storage, without wiping it
+    pub fn into_inner(self) -> S {
+        let this = ManuallyDrop::new(self);
+        unsafe { core::ptr::read(&this.0) }
+    }
+
+    /// Allocate a new storage that can hold at least `new_capacity` elements,
+    /// move the contents of the old storage into it, then wipe the old storage
+    #[cold]
+    #[inline(never)]
+    fn grow<T>(&mut self, new_capacity: usize, growth: Growth, on_failure: OnFailure) -> bool
+    where
+        S: StorageWithCapacity<T>,
+    {
+        let old_capacity = Storage::<T>::capacity(&self.0);
+        let mut storage = S::default();
+
+        // grow by at least doubling, but if that's not possible, or an exact
+        // capacity was requested, then only grow as much as requested
+        let reserved = match growth {
+            Growth::Amortized => {
+                Storage::<T>::try_reserve(&mut storage, old_capacity.saturating_mul(2).max(new_capacity))
+            }
+            Growth::Exact => false,
+        };
+
+        if !reserved {
+            match on_failure {
+                OnFailure::Abort => Storage::<T>::__reserve_exact__(&mut storage, new_capacity),
+                OnFailure::Error => {
+                    if !Storage::<T>::__try_reserve_exact__(&mut storage, new_capacity) {
+                        return false
+                    }
+                }
+            }
+        }
+
+        // Safety
+        //
+        // * the new storage has at least `old_capacity` capacity, and it's a different storage
+        //   so they can't overlap
+        // * we wipe the old storage, then drop it without the `Zeroizing` wrapper
+        //   so it doesn't get wiped twice
+        unsafe {
+            let len = old_capacity * size_of::<T>();
+            let old = Storage::<u8>::as_mut_ptr(&mut self.0);
+            Storage::<u8>::as_mut_ptr(&mut storage).copy_from_nonoverlapping(old, len);
+            wipe(old, Storage::<u8>::capacity(&self.0));
+        }
+
+        drop(core::mem::replace(&mut self.0, storage));
+
+        true
+    }
+}
+
+impl<S: ?Sized + Storage<u8>> Zeroizing<S> {
+    /// Get a reference to the underlying storage
+    pub fn get(&self) -> &S { &self.0 }
+}
+
+impl<S: Storage<u8> + Default> Default for Zeroizing<S> {
+    fn default() -> Self { Self(S::default()) }
+}
+
+unsafe impl<T, S: StorageWithCapacity<T> + Storage<u8>> Storage<T> for Zeroizing<S> {
+    #[doc(hidden)]
+    const CONST_CAPACITY: Option<usize> = <S as Storage<T>>::CONST_CAPACITY;
+    const IS_ALIGNED: bool = <S as Storage<T>>::IS_ALIGNED;
+
+    #[inline]
+    fn capacity(&self) -> usize { Storage::<T>::capacity(&self.0) }
+
+    #[inline]
+    fn as_ptr(&self) -> *const T { Storage::<T>::as_ptr(&self.0) }
+
+    #[inline]
+    fn as_mut_ptr(&mut self) -> *mut T { Storage::<T>::as_mut_ptr(&mut self.0) }
+
+    #[inline]
+    fn reserve(&mut self, new_capacity: usize) {
+        if Storage::<T>::capacity(&self.0) < new_capacity {
+            self.grow::<T>(new_capacity, Growth::Amortized, OnFailure::Abort);
+        }
+    }
+
+    #[inline]
+    fn try_reserve(&mut self, new_capacity: usize) -> bool {
+        Storage::<T>::capacity(&self.0) >= new_capacity
+            || self.grow::<T>(new_capacity, Growth::Amortized, OnFailure::Error)
+    }
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    fn __reserve_exact__(&mut self, new_capacity: usize) {
+        if Storage::<T>::capacity(&self.0) < new_capacity {
+            self.grow::<T>(new_capacity, Growth::Exact, OnFailure::Abort);
+        }
+    }
+
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    fn __try_reserve_exact__(&mut self, new_capacity: usize) -> bool {
+        Storage::<T>::capacity(&self.0) >= new_capacity || self.grow::<T>(new_capacity, Growth::Exact, OnFailure::Error)
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    #[allow(non_snake_case)]
+    unsafe fn __wipe__(&mut self, index: usize, len: usize) {
+        unsafe {
+            wipe(
+                Storage::<T>::as_mut_ptr(&mut self.0).add(index).cast(),
+                len * size_of::<T>(),
+            )
+        }
+    }
+
+    #[doc(hidden)]
+    #[inline(always)]
+    #[allow(non_snake_case)]
+    fn __verify__(&self) { Storage::<T>::__verify__(&self.0) }
+
+    #[doc(hidden)]
+    #[inline(always)]
+    #[allow(non_snake_case)]
+    unsafe fn __on_drop__(&mut self, len: usize) -> bool { unsafe { Storage::<T>::__on_drop__(&mut self.0, len) } }
+}
+
+unsafe impl<T, S: StorageWithCapacity<T> + FixedCapacity<T> + Storage<u8>> FixedCapacity<T> for Zeroizing<S> {
+    const CAPACITY: usize = <S as FixedCapacity<T>>::CAPACITY;
+}
+
+unsafe impl<T, S: StorageWithCapacity<T> + Storage<u8>> StorageWithCapacity<T> for Zeroizing<S> {
+    #[inline]
+    fn with_capacity(capacity: usize) -> Self { Self(<S as StorageWithCapacity<T>>::with_capacity(capacity)) }
+
+    #[doc(hidden)]
+    #[inline]
+    #[allow(non_snake_case)]
+    fn __with_capacity__const_capacity_checked(capacity: usize, old_capacity: Option<usize>) -> Self {
+        Self(<S as StorageWithCapacity<T>>::__with_capacity__const_capacity_checked(
+            capacity,
+            old_capacity,
+        ))
+    }
+}
@@ -0,0 +1,118 @@
+use crate::{raw::Storage, GenericVec, SliceVec};
+
+use core::{
+    marker::PhantomData,
+    mem::{ManuallyDrop, MaybeUninit},
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+};
+
+/// A [`SliceVec`] view over the storage of another vector
+///
+/// This struct is created by [`GenericVec::as_slice_vec`].
+/// See its documentation for more.
+///
+/// When the view is dropped, the length is written back to the vector, and
+/// the storage of the vector is told about any elements that were removed
+pub struct SliceVecGuard<'a, T, S: ?Sized + Storage<T>> {
+    len: &'a mut usize,
+    old_len: usize,
+    // a pointer, so that it doesn't alias the view
+    storage: NonNull<S>,
+    ptr: *const T,
+    capacity: usize,
+    vec: ManuallyDrop<SliceVec<'a, T>>,
+    mark: PhantomData<&'a mut S>,
+}
+
+impl<T, S: ?Sized + Storage<T>> GenericVec<T, S> {
+    /// Borrow the whole storage of this vector as a [`SliceVec`]
+    ///
+    /// This lets non-generic functions that take a `&mut SliceVec<'_, T>` work with
+    /// any vector. The view has the same elements and capacity as this vector, but it
+    /// can't grow, even if the storage could. Any changes made through the view are visible
+    /// in this vector after the view is dropped.
+    ///
+    /// If the view is leaked, this vector will be empty, and the elements will be leaked
+    ///
+    /// ```rust
+    /// use generic_vec::{ArrayVec, SliceVec};
+    ///
+    /// fn keep_odd(vec: &mut SliceVec<'_, i32>) {
+    ///     vec.retain(|x| *x % 2 == 1);
+    ///     vec.push(7);
+    /// }
+    ///
+    /// let mut vec = ArrayVec::<i32, 8>::new();
+    /// vec.extend_from_slice(&[1, 2, 3, 4, 5]);
+    /// keep_odd(&mut vec.as_slice_vec());
+    /// assert_eq!(vec, [1, 3, 5, 7]);
+    /// ```
+    pub fn as_slice_vec(&mut self) -> SliceVecGuard<'_, T, S> {
+        let len = self.len();
+        let capacity = self.capacity();
+        let mut storage = NonNull::from(&mut self.storage);
+        let ptr = unsafe { storage.as_mut().as_mut_ptr() };
+        self.len = 0;
+
+        // Safety
+        //
+        // * the storage is valid for `capacity` elements, and is borrowed for the
+        //   lifetime of the guard
+        // * the first `len` elements are initialized
+        // * the length of this vector is zero until the guard is dropped, so
+        //   the elements are only owned by the view
+        unsafe {
+            let mut vec = SliceVec::new(core::slice::from_raw_parts_mut(ptr.cast::<MaybeUninit<T>>(), capacity));
+            vec.set_len_unchecked(len);
+
+            SliceVecGuard {
+                len: &mut self.len,
+                old_len: len,
+                storage,
+                ptr,
+                capacity,
+                vec: ManuallyDrop::new(vec),
+                mark: PhantomData,
+            }
+        }
+    }
+}
+
+impl<T, S: ?Sized + Storage<T>> Drop for SliceVecGuard<'_, T, S> {
+    fn drop(&mut self) {
+        // the view could have been swapped out with another one, so only
+        // give the elements back if it's still a view of the same storage
+        if self.vec.as_ptr() == self.ptr && self.vec.capacity() == self.capacity {
+            let len = self.vec.len();
+            *self.len = len;
+
+            // Safety
+            //
+            // * the view is no longer used, so the storage can be accessed again
+            // * the elements in `len..old_len` were removed through the view
+            unsafe {
+                let storage = self.storage.as_mut();
+
+                if len < self.old_len {
+                    storage.__wipe__(len, self.old_len - len);
+                }
+
+                #[cfg(feature = "debug-checks")]
+                storage.__verify__();
+            }
+        } else {
+            unsafe { ManuallyDrop::drop(&mut self.vec) }
+        }
+    }
+}
+
+impl<'a, T, S: ?Sized + Storage<T>> Deref for SliceVecGuard<'a, T, S> {
+    type Target = SliceVec<'a, T>;
+
+    fn deref(&self) -> &Self::Target { &self.vec }
+}
+
+impl<T, S: ?Sized + Storage<T>> DerefMut for SliceVecGuard<'_, T, S> {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.vec }
+}
@@ -0,0 +1,54 @@
+use crate::{raw::VecStorage, GenericVec};
+
+use core::ops::{Deref, DerefMut};
+use std::vec::Vec;
+
+/// A [`GenericVec`] view over a borrowed [`Vec`]
+///
+/// This struct is created by [`GenericVec::from_std_vec_mut`].
+/// See its documentation for more.
+///
+/// When the view is dropped, the elements are given back to the `Vec`
+pub struct StdVecView<'a, T> {
+    vec: GenericVec<T, VecStorage<'a, T>>,
+}
+
+impl<'a, T> GenericVec<T, VecStorage<'a, T>> {
+    /// Borrow the allocation and elements of a [`Vec`], so that it can be
+    /// used as a `GenericVec`
+    ///
+    /// Any changes made through the view are visible in `vec` after the view is dropped
+    ///
+    /// ```rust
+    /// # use generic_vec::GenericVec;
+    /// let mut vec = vec![1, 2, 3, 4, 5, 6];
+    ///
+    /// {
+    ///     let mut view = GenericVec::from_std_vec_mut(&mut vec);
+    ///     view.drain_filter(1..5, |x| *x % 2 == 0);
+    ///     view.push(7);
+    /// }
+    ///
+    /// assert_eq!(vec, [1, 3, 5, 6, 7]);
+    /// ```
+    pub fn from_std_vec_mut(vec: &'a mut Vec<T>) -> StdVecView<'a, T> {
+        let (len, storage) = VecStorage::new(vec);
+
+        // Safety
+        //
+        // the first `len` elements in the `Vec` are initialized
+        let vec = unsafe { GenericVec::from_raw_parts(len, storage) };
+
+        StdVecView { vec }
+    }
+}
+
+impl<'a, T> Deref for StdVecView<'a, T> {
+    type Target = GenericVec<T, VecStorage<'a, T>>;
+
+    fn deref(&self) -> &Self::Target { &self.vec }
+}
+
+impl<T> DerefMut for StdVecView<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.vec }
+}
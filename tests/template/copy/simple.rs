@@ -25,7 +25,6 @@ pub fn simple() {
 }
 
 #[test]
-#[cfg(feature = "nightly")]
 pub fn array_ops() {
     let output = mockalloc::record_allocs(|| {
         new_vec!(mut vec, max(8));
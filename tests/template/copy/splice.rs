@@ -48,3 +48,18 @@ fn splice_from_zero() {
 
     assert_eq!(output.mem_allocated(), output.mem_freed() + leak!(splice));
 }
+
+#[test]
+fn splice_unknown_len() {
+    let output = mockalloc::record_allocs(|| {
+        new_vec!(mut vec, max(16));
+
+        vec.extend([0, 1, 2, 3, 4, 5, 6, 7].iter().copied());
+
+        vec.splice(2..3, (10..20).filter(|x| x % 2 == 0));
+
+        assert_eq!(vec, [0, 1, 10, 12, 14, 16, 18, 3, 4, 5, 6, 7]);
+    });
+
+    assert_eq!(output.mem_allocated(), output.mem_freed() + leak!(splice));
+}
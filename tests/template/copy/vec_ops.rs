@@ -39,3 +39,65 @@ fn grow() {
 
     assert_eq!(output.mem_allocated(), output.mem_freed() + leak!(simple));
 }
+
+#[test]
+fn extend_inexact() {
+    let output = mockalloc::record_allocs(|| {
+        new_vec!(mut vec, max(8));
+        // the upper bound is larger than the capacity, but all items fit
+        vec.extend((0..32).filter(|x| x % 4 == 0));
+        assert_eq!(vec, [0, 4, 8, 12, 16, 20, 24, 28]);
+
+        vec.clear();
+        vec.extend(&[1, 2, 3]);
+        vec.extend([4, 5].iter().chain(&[6, 7]));
+        assert_eq!(vec, [1, 2, 3, 4, 5, 6, 7]);
+    });
+
+    assert_eq!(output.mem_allocated(), output.mem_freed() + leak!(simple));
+}
+
+#[test]
+fn copy_ops() {
+    let output = mockalloc::record_allocs(|| {
+        new_vec!(mut vec, max(8));
+        vec.extend_from_copy_slice(&[1, 2, 3]);
+        vec.grow_copy(2, 9);
+        assert_eq!(vec, [1, 2, 3, 9, 9]);
+
+        vec.resize_copy(7, 4);
+        assert_eq!(vec, [1, 2, 3, 9, 9, 4, 4]);
+        vec.resize_copy(2, 0);
+        assert_eq!(vec, [1, 2]);
+
+        vec.clone_from_copy_slice(&[5, 6, 7, 8]);
+        assert_eq!(vec, [5, 6, 7, 8]);
+    });
+
+    assert_eq!(output.mem_allocated(), output.mem_freed() + leak!(simple));
+}
+
+#[test]
+fn spare_capacity() {
+    let output = mockalloc::record_allocs(|| {
+        new_vec!(mut vec, max(8));
+        vec.extend([1, 2].iter().copied());
+        vec.reserve(6);
+
+        let pushed = vec.with_spare_capacity(|spare| {
+            spare.push(3);
+            spare.push(4);
+            spare.len()
+        });
+        assert_eq!(pushed, 2);
+        assert_eq!(vec, [1, 2, 3, 4]);
+
+        let (init, mut spare) = vec.split_at_spare_mut();
+        spare.extend(init.iter().map(|x| x * 2));
+        init[0] = 0;
+        unsafe { generic_vec::save_spare!(spare, &mut vec) }
+        assert_eq!(vec, [0, 2, 3, 4, 2, 4, 6, 8]);
+    });
+
+    assert_eq!(output.mem_allocated(), output.mem_freed() + leak!(simple));
+}
@@ -9,3 +9,45 @@ pub fn into_iter() {
 
     assert_eq!(output.mem_allocated(), output.mem_freed() + leak!(simple));
 }
+
+#[test]
+pub fn into_vec() {
+    let output = mockalloc::record_allocs(|| {
+        new_vec!(mut vec, max(8));
+        vec.extend((0..8).map(|x| S!(x)));
+
+        let mut iter = vec.into_iter();
+        assert_eq!(iter.next(), Some(S!(0)));
+        assert_eq!(iter.next(), Some(S!(1)));
+        assert_eq!(iter.next_back(), Some(S!(7)));
+
+        let mut vec = iter.into_vec();
+        assert_eq!(vec, S!([2, 3, 4, 5, 6]));
+
+        vec.push(S!(8));
+        assert_eq!(vec, S!([2, 3, 4, 5, 6, 8]));
+    });
+
+    assert_eq!(output.mem_allocated(), output.mem_freed() + leak!(simple));
+}
+
+#[test]
+pub fn into_storage() {
+    let output = mockalloc::record_allocs(|| {
+        new_vec!(mut vec, max(8));
+        vec.extend((0..8).map(|x| S!(x)));
+        let capacity = vec.capacity();
+
+        let mut iter = vec.into_iter();
+        assert_eq!(iter.next(), Some(S!(0)));
+
+        let mut vec = generic_vec::GenericVec::with_storage(iter.into_storage());
+        assert!(vec.is_empty());
+        assert_eq!(vec.capacity(), capacity);
+
+        vec.extend((0..4).map(|x| S!(x)));
+        assert_eq!(vec, S!([0, 1, 2, 3]));
+    });
+
+    assert_eq!(output.mem_allocated(), output.mem_freed() + leak!(simple));
+}
@@ -42,3 +42,18 @@ fn splice_from_zero() {
 
     assert_eq!(output.mem_allocated(), output.mem_freed() + leak!(splice));
 }
+
+#[test]
+fn splice_unknown_len() {
+    let output = mockalloc::record_allocs(|| {
+        new_vec!(mut vec, max(16));
+
+        vec.extend([0, 1, 2, 3, 4, 5, 6, 7].iter().map(|x| S!(x)));
+
+        vec.splice(2..3, [10, 11, 12, 13, 14, 15].iter().filter(|x| *x % 2 == 0).map(|x| S!(x)));
+
+        assert_eq!(vec, S!([0, 1, 10, 12, 14, 3, 4, 5, 6, 7]));
+    });
+
+    assert_eq!(output.mem_allocated(), output.mem_freed() + leak!(splice));
+}
@@ -81,7 +81,6 @@ macro_rules! leak {
     };
 }
 
-#[cfg(feature = "nightly")]
 mod array_vec {
     macro_rules! new_vec {
         ($vec:pat, max($len:expr)) => {
@@ -103,7 +102,6 @@ mod slice_vec {
     make_tests_files!();
 }
 
-#[cfg(feature = "nightly")]
 mod init_array_vec {
     macro_rules! new_vec {
         ($vec:pat, max($len:expr)) => {
@@ -125,6 +123,303 @@ mod heap_vec {
     }
 
     make_tests_files!();
+
+    #[test]
+    fn extend_reserves_once() {
+        let output = mockalloc::record_allocs(|| {
+            let mut vec = generic_vec::HeapVec::new();
+            vec.extend(0..100);
+            vec.extend([1, 2, 3].iter());
+            assert_eq!(vec.len(), 103);
+        });
+
+        // one allocation for the exact size hint, and one to grow for the slice
+        assert_eq!(output.num_allocs(), 2);
+
+        let output = mockalloc::record_allocs(|| {
+            let vec: generic_vec::HeapVec<_> = (0..100).filter(|x| x % 2 == 0).collect();
+            assert_eq!(vec.len(), 50);
+            assert_eq!(vec[49], 98);
+        });
+
+        assert_eq!(output.mem_allocated(), output.mem_freed());
+    }
+
+    #[test]
+    fn collect_uses_size_hint() {
+        use generic_vec::{raw::Counting, GenericVec};
+
+        let vec: GenericVec<i32, Counting<generic_vec::raw::Heap<i32>>> = (0..100).collect();
+        assert_eq!(vec.len(), 100);
+
+        // the storage was created with enough capacity, so it never needed to reserve
+        let stats = vec.storage().stats();
+        assert_eq!(stats.reserve_calls, 0);
+        assert_eq!(stats.peak_capacity, 100);
+    }
+
+    #[test]
+    fn from_copy_slice_does_not_clone() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        static CLONES: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Copy, Debug, PartialEq)]
+        struct Counted(u32);
+
+        #[allow(clippy::non_canonical_clone_impl)]
+        impl Clone for Counted {
+            fn clone(&self) -> Self {
+                CLONES.fetch_add(1, Ordering::Relaxed);
+                Counted(self.0)
+            }
+        }
+
+        let slice = [Counted(1), Counted(2), Counted(3)];
+        let vec = generic_vec::HeapVec::from(&slice[..]);
+        assert_eq!(vec, slice);
+
+        let mut vec = generic_vec::ArrayVec::<Counted, 4>::from(&slice[..2]);
+        vec.extend_from_copy_slice(&slice[2..]);
+        assert_eq!(vec, slice);
+
+        let buffer = generic_vec::raw::UninitBuffer::<[Counted; 4]>::uninit();
+        let vec = generic_vec::GenericVec::from_copy_slice_in(buffer, &slice);
+        assert_eq!(vec, slice);
+
+        let vec = generic_vec::GenericVec::from_copy_elem_in(generic_vec::raw::Heap::<Counted>::new(), Counted(4), 3);
+        assert_eq!(vec, [Counted(4); 3]);
+
+        let mut vec = vec.clone_copy();
+        assert_eq!(vec, [Counted(4); 3]);
+        vec.repeat_copy(2);
+        assert_eq!(vec, [Counted(4); 6]);
+
+        assert_eq!(CLONES.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn splice_in_capacity_does_not_allocate() {
+        let mut vec: generic_vec::HeapVec<i32> = (0..8).collect();
+        vec.reserve(100);
+
+        let output = mockalloc::record_allocs(|| {
+            vec.splice(2..3, (0..50).filter(|x| x % 2 == 0));
+            vec.splice(0..0, 0..10);
+        });
+
+        assert_eq!(output.num_allocs(), 0);
+        assert_eq!(vec.len(), 42);
+        assert_eq!(vec[..12], [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 0, 1]);
+        assert_eq!(vec[12..15], [0, 2, 4]);
+        assert_eq!(vec[35..], [46, 48, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn recycle() {
+        let output = mockalloc::record_allocs(|| {
+            let vec: generic_vec::HeapVec<u64> = (0..8).collect();
+            let ptr = vec.as_ptr() as usize;
+
+            let mut bytes = vec.recycle::<u8>();
+            assert!(bytes.is_empty());
+            assert_eq!(bytes.capacity(), 64);
+
+            bytes.extend(0..64);
+            assert_eq!(bytes.as_ptr() as usize, ptr);
+
+            let vec: generic_vec::HeapVec<u64> = bytes.recycle();
+            assert!(vec.is_empty());
+            assert_eq!(vec.capacity(), 8);
+        });
+
+        // only the initial allocation
+        assert_eq!(output.num_allocs(), 1);
+    }
+
+    #[test]
+    fn map_in_place() {
+        let vec: generic_vec::HeapVec<_> = (0..10).map(|x| S!(x)).collect();
+        let ptr = vec.as_ptr() as usize;
+
+        let output = mockalloc::record_allocs(|| {
+            let lens = vec.map_in_place(|x| x.len() as u8);
+            assert_eq!(lens.as_ptr() as usize, ptr);
+            assert_eq!(lens, [1; 10]);
+        });
+
+        assert_eq!(output.num_allocs(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn map_in_place_unwind() {
+        let rc = std::rc::Rc::new(());
+        let vec: generic_vec::HeapVec<_> = (0..10).map(|_| rc.clone()).collect();
+        let mut count = 0;
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            vec.map_in_place(|x| {
+                count += 1;
+                if count == 5 {
+                    panic!("oops")
+                }
+                Some(x)
+            })
+        }));
+
+        assert!(result.is_err());
+        // all the elements are dropped, both the mapped and the unmapped ones
+        assert_eq!(std::rc::Rc::strong_count(&rc), 1);
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod boxed_slice {
+    use core::mem::MaybeUninit;
+    use generic_vec::{raw::BoxedSlice, GenericVec};
+    use std::{boxed::Box, vec::Vec};
+
+    macro_rules! new_vec {
+        ($vec:pat, max($len:expr)) => {
+            let $vec = generic_vec::GenericVec::with_storage(generic_vec::raw::BoxedSlice::new($len));
+        };
+    }
+
+    make_tests_files!();
+
+    #[test]
+    fn allocates_once() {
+        let output = mockalloc::record_allocs(|| {
+            let mut vec = GenericVec::<u32, BoxedSlice<u32>>::with_capacity(10);
+            assert_eq!(vec.capacity(), 10);
+            vec.extend(0..10);
+            assert!(!vec.try_reserve(11));
+            assert!(vec.try_push(10).is_err());
+        });
+
+        assert_eq!(output.num_allocs(), 1);
+        assert_eq!(output.mem_allocated(), output.mem_freed());
+    }
+
+    #[test]
+    fn into_boxed_slice() {
+        let mut vec = GenericVec::<u32, BoxedSlice<u32>>::with_capacity(4);
+        vec.extend(0..4);
+        let ptr = vec.as_ptr() as usize;
+
+        let output = mockalloc::record_allocs(|| {
+            let boxed: Box<[u32]> = vec.into();
+            assert_eq!(boxed.as_ptr() as usize, ptr);
+            assert_eq!(*boxed, [0, 1, 2, 3]);
+
+            let vec = GenericVec::<u32, BoxedSlice<u32>>::from(boxed);
+            assert_eq!(vec.as_ptr() as usize, ptr);
+            assert_eq!(vec, [0, 1, 2, 3]);
+        });
+
+        assert_eq!(output.num_allocs(), 0);
+    }
+
+    #[test]
+    fn into_boxed_slice_zero_sized() {
+        #[derive(Debug, PartialEq)]
+        struct Zst;
+
+        let mut vec = GenericVec::<Zst, BoxedSlice<Zst>>::with_capacity(4);
+        vec.push(Zst);
+        vec.push(Zst);
+
+        let boxed: Box<[Zst]> = vec.into();
+        assert_eq!(*boxed, [Zst, Zst]);
+
+        let vec = GenericVec::<Zst, BoxedSlice<Zst>>::from(boxed);
+        assert_eq!(vec, [Zst, Zst]);
+    }
+
+    #[test]
+    fn box_storage() {
+        let output = mockalloc::record_allocs(|| {
+            let storage: Box<[MaybeUninit<_>]> = Box::new(generic_vec::uninit_array!(8));
+            let mut vec = GenericVec::with_storage(storage);
+            vec.extend((0..5).map(|x| S!(x)));
+            assert!(!vec.try_reserve(9));
+
+            let boxed: Box<[_]> = vec.into();
+            assert_eq!(*boxed, S!([0, 1, 2, 3, 4]));
+        });
+
+        assert_eq!(output.mem_allocated(), output.mem_freed());
+    }
+
+    #[test]
+    fn vec_storage() {
+        let output = mockalloc::record_allocs(|| {
+            let mut vec = GenericVec::with_storage(Vec::<MaybeUninit<_>>::new());
+            vec.extend((0..5).map(|x| S!(x)));
+            vec.reserve(100);
+            assert!(vec.capacity() >= 105);
+
+            let std_vec: Vec<_> = vec.into();
+            assert_eq!(std_vec, S!([0, 1, 2, 3, 4]));
+
+            let mut vec = GenericVec::<_, Vec<MaybeUninit<_>>>::from(std_vec);
+            vec.push(S!(5));
+            assert_eq!(vec, S!([0, 1, 2, 3, 4, 5]));
+        });
+
+        assert_eq!(output.mem_allocated(), output.mem_freed());
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod aligned_heap_vec {
+    use generic_vec::{AlignedHeapVec, HeapVec};
+
+    macro_rules! new_vec {
+        ($vec:pat, max($len:expr)) => {
+            let $vec = generic_vec::AlignedHeapVec::<_, 64>::new();
+        };
+    }
+
+    make_tests_files!();
+
+    #[test]
+    fn aligned() {
+        let mut vec = AlignedHeapVec::<u8, 4096>::new();
+        assert_eq!(vec.as_ptr() as usize % 4096, 0);
+
+        for i in 0..10_000 {
+            vec.push(i as u8);
+            assert_eq!(vec.as_ptr() as usize % 4096, 0);
+        }
+
+        let vec = AlignedHeapVec::<u64, 4096>::with_capacity(3);
+        assert_eq!(vec.as_ptr() as usize % 4096, 0);
+    }
+
+    #[test]
+    fn heap_vec_conversions() {
+        let output = mockalloc::record_allocs(|| {
+            let vec: HeapVec<u32> = (0..10).collect();
+            let ptr = vec.as_ptr() as usize;
+
+            // the alignment of `u32` is enough, so the allocation is reused
+            let vec = AlignedHeapVec::<u32, 4>::from(vec);
+            assert_eq!(vec.as_ptr() as usize, ptr);
+            let vec = HeapVec::from(vec);
+            assert_eq!(vec.as_ptr() as usize, ptr);
+
+            let vec = AlignedHeapVec::<u32, 32>::from(vec);
+            assert_eq!(vec.as_ptr() as usize % 32, 0);
+            assert!((0..10).eq(vec.iter().copied()));
+
+            let vec = HeapVec::from(vec);
+            assert!((0..10).eq(vec.iter().copied()));
+        });
+
+        assert_eq!(output.mem_allocated(), output.mem_freed());
+    }
 }
 
 mod init_slice_vec {
@@ -140,6 +435,77 @@ mod init_slice_vec {
     make_tests_files!(copy_only);
 }
 
+#[cfg(feature = "alloc")]
+mod std_vec {
+    use generic_vec::GenericVec;
+    use std::vec::Vec;
+
+    #[test]
+    fn view() {
+        let mut vec: Vec<_> = (0..8).collect();
+
+        {
+            let mut view = GenericVec::from_std_vec_mut(&mut vec);
+            assert_eq!(view[..], [0, 1, 2, 3, 4, 5, 6, 7]);
+
+            view.cursor(2..6).drop_front();
+            view.drain(3..6);
+            view.drain_filter(.., |x| *x == 1);
+            view.extend(10..20);
+            assert_eq!(view.len(), 13);
+        }
+
+        assert_eq!(vec[..3], [0, 3, 7]);
+        assert!((10..20).eq(vec[3..].iter().copied()));
+    }
+
+    #[test]
+    fn reuses_allocation() {
+        let mut vec: Vec<u32> = Vec::with_capacity(16);
+        vec.push(0);
+        let ptr = vec.as_ptr() as usize;
+
+        let output = mockalloc::record_allocs(|| {
+            let mut view = GenericVec::from_std_vec_mut(&mut vec);
+            view.extend(1..16);
+            assert!(!view.try_reserve(usize::MAX / 8));
+        });
+
+        assert_eq!(output.num_allocs(), 0);
+        assert_eq!(vec.as_ptr() as usize, ptr);
+        assert!((0..16).eq(vec.iter().copied()));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn write_back_on_unwind() {
+        let mut vec = vec![S!(0), S!(1)];
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let mut view = GenericVec::from_std_vec_mut(&mut vec);
+            view.push(S!(2));
+            panic!("oops");
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(vec, S!([0, 1, 2]));
+    }
+
+    #[test]
+    fn write_back_on_drop() {
+        let mut vec = std::vec![S!(0), S!(1)];
+
+        {
+            let (len, storage) = generic_vec::raw::VecStorage::new(&mut vec);
+            let mut gvec = unsafe { GenericVec::from_raw_parts(len, storage) };
+            gvec.push(S!(2));
+            gvec.remove(0);
+        }
+
+        assert_eq!(vec, S!([1, 2]));
+    }
+}
+
 #[cfg(feature = "std")]
 mod zero_sized {
     use core::cell::Cell;
@@ -175,3 +541,1505 @@ mod zero_sized {
         assert_eq!(get(), 3);
     }
 }
+
+#[cfg(feature = "rayon")]
+mod rayon {
+    use generic_vec::{gvec, HeapVec, TypeVec};
+    use rayon::prelude::*;
+
+    #[test]
+    fn into_par_iter() {
+        let vec: HeapVec<i32> = (0..1000).collect();
+        assert_eq!((&vec).into_par_iter().sum::<i32>(), 499500);
+
+        let mut vec = vec;
+        (&mut vec).into_par_iter().for_each(|x| *x *= 2);
+        assert_eq!(vec.into_par_iter().sum::<i32>(), 999000);
+    }
+
+    #[test]
+    fn par_drain() {
+        let mut vec: HeapVec<String> = (0..100).map(|x| x.to_string()).collect();
+        let drained: Vec<String> = vec.par_drain(10..90).collect();
+        assert_eq!(drained.len(), 80);
+        assert_eq!(drained[0], "10");
+        assert_eq!(vec.len(), 20);
+        assert_eq!(vec[9], "9");
+        assert_eq!(vec[10], "90");
+
+        // dropping without driving removes the range
+        drop(vec.par_drain(..10));
+        assert_eq!(vec.len(), 10);
+        assert_eq!(vec[0], "90");
+    }
+
+    #[test]
+    fn par_extend() {
+        let mut vec: HeapVec<i32> = gvec![-1];
+        vec.par_extend((0..100).into_par_iter());
+        vec.par_extend((0..100).into_par_iter().filter(|x| x % 2 == 0));
+        assert_eq!(vec.len(), 151);
+        assert_eq!(vec[..4], [-1, 0, 1, 2]);
+        assert_eq!(vec[101..104], [0, 2, 4]);
+    }
+
+    #[test]
+    fn from_par_iter() {
+        let vec: TypeVec<i32, [i32; 16]> = (0..16).into_par_iter().collect();
+        assert_eq!(vec, (0..16).collect::<Vec<_>>());
+    }
+}
+
+#[cfg(all(feature = "zeroize", feature = "alloc"))]
+mod zeroizing {
+    use generic_vec::{
+        gvec,
+        raw::{Heap, Zeroizing},
+        GenericVec, HeapVec,
+    };
+
+    fn spare(vec: &GenericVec<u8, Zeroizing<Heap<u8>>>) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(vec.as_ptr().add(vec.len()), vec.capacity() - vec.len()) }
+    }
+
+    #[test]
+    fn wipe_on_remove() {
+        let mut vec = GenericVec::with_storage(Zeroizing::new(Heap::<u8>::new()));
+        vec.extend_from_slice(&[0xff; 16]);
+
+        vec.truncate(12);
+        assert_eq!(vec.pop(), 0xff);
+        assert_eq!(vec.remove(0), 0xff);
+        assert_eq!(vec.swap_remove(0), 0xff);
+        vec.drain(4..6);
+        assert_eq!(vec, [0xff; 7]);
+        assert!(spare(&vec)[..9].iter().all(|&x| x == 0));
+    }
+
+    #[test]
+    fn grow() {
+        let mut vec = GenericVec::with_storage(Zeroizing::new(Heap::<u8>::new()));
+        for i in 0..100_u8 {
+            vec.push(i);
+        }
+        assert_eq!(vec, (0..100).collect::<std::vec::Vec<_>>());
+        assert!(vec.try_reserve(10));
+    }
+
+    #[test]
+    fn grow_within_limit() {
+        use generic_vec::raw::{Limited, Storage};
+
+        let mut vec = GenericVec::with_storage(Limited::new(Zeroizing::new(Heap::<u32>::new()), 10));
+        for i in 0..10 {
+            vec.push(i);
+            assert!(Storage::<u32>::capacity(vec.storage().get()) <= 10);
+        }
+
+        assert_eq!(vec, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(Storage::<u32>::capacity(vec.storage().get()), 10);
+    }
+
+    #[test]
+    fn wipe_on_drop() {
+        let mut buf = [0xff_u8; 8];
+        drop(Zeroizing::new(&mut buf[..]));
+        assert_eq!(buf, [0; 8]);
+    }
+
+    #[test]
+    fn zeroize() {
+        let mut vec: HeapVec<u32> = gvec![1, 2, 3, 4];
+        vec.zeroize();
+        assert!(vec.is_empty());
+        unsafe { vec.set_len_unchecked(4) };
+        assert_eq!(vec, [0; 4]);
+    }
+}
+
+#[cfg(all(feature = "debug-checks", feature = "alloc"))]
+mod checked {
+    use generic_vec::{
+        raw::{Checked, Heap},
+        GenericVec,
+    };
+
+    macro_rules! new_vec {
+        ($vec:pat, max($len:expr)) => {
+            let $vec = crate::checked::new_checked_vec();
+        };
+    }
+
+    make_tests_files!();
+
+    pub fn new_checked_vec<T>() -> GenericVec<T, Checked<Heap<T>>> {
+        GenericVec::with_storage(Checked::new(Heap::new()))
+    }
+
+    const POISON: u8 = Checked::<Heap<u8>>::POISON;
+
+    fn spare(vec: &GenericVec<u8, Checked<Heap<u8>>>) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(vec.as_ptr().add(vec.len()), vec.capacity() - vec.len()) }
+    }
+
+    #[test]
+    fn grow_within_limit() {
+        use generic_vec::raw::{Limited, Storage};
+
+        let mut vec = GenericVec::with_storage(Limited::new(Checked::new(Heap::<u32>::new()), 10));
+        for i in 0..10 {
+            vec.push(i);
+            assert!(Storage::<u32>::capacity(vec.storage().get()) <= 10);
+        }
+
+        assert_eq!(vec, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(Storage::<u32>::capacity(vec.storage().get()), 10);
+    }
+
+    #[test]
+    fn poison() {
+        let mut vec = new_checked_vec::<u8>();
+        vec.reserve(10);
+        assert!(spare(&vec).iter().all(|&x| x == POISON));
+
+        vec.extend_from_slice(&[1, 2, 3, 4, 5, 6]);
+        vec.truncate(4);
+        assert_eq!(vec.remove(0), 1);
+        vec.drain(1..2);
+        assert_eq!(vec, [2, 4]);
+        assert!(spare(&vec).iter().all(|&x| x == POISON));
+
+        for i in 0..100 {
+            vec.push(i);
+        }
+        assert!(spare(&vec).iter().all(|&x| x == POISON));
+        vec.storage().verify();
+    }
+
+    #[test]
+    fn with_capacity() {
+        let vec = GenericVec::<u32, Checked<Heap<u32>>>::with_capacity(10);
+        assert!(vec.capacity() >= 10);
+        vec.storage().verify();
+
+        let mut vec = new_checked_vec::<()>();
+        vec.push(());
+        assert_eq!(vec.len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn canary() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let mut vec = new_checked_vec::<u8>();
+        vec.extend_from_slice(&[1, 2, 3]);
+
+        // write one byte past the capacity
+        unsafe {
+            let capacity = vec.capacity();
+            vec.as_mut_ptr().add(capacity).write(0);
+        }
+
+        assert!(catch_unwind(AssertUnwindSafe(|| {
+            vec.push(4);
+        }))
+        .is_err());
+        assert!(catch_unwind(AssertUnwindSafe(|| drop(vec))).is_err());
+    }
+
+    #[test]
+    fn slice_vec_view() {
+        let mut vec = new_checked_vec::<u8>();
+        vec.extend_from_slice(&[1, 2, 3, 4, 5, 6]);
+
+        {
+            let mut view = vec.as_slice_vec();
+            view.truncate(4);
+            view.remove(0);
+        }
+
+        assert_eq!(vec, [2, 3, 4]);
+        assert!(spare(&vec).iter().all(|&x| x == POISON));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn slice_vec_view_verifies() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let mut vec = new_checked_vec::<u8>();
+        vec.reserve(8);
+        vec.extend_from_slice(&[1, 2, 3]);
+
+        let view = vec.as_slice_vec();
+        unsafe {
+            let capacity = view.capacity();
+            (view.as_ptr() as *mut u8).add(capacity).write(0);
+        }
+
+        assert!(catch_unwind(AssertUnwindSafe(|| drop(view))).is_err());
+        assert!(catch_unwind(AssertUnwindSafe(|| drop(vec))).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn with_spare_capacity_verifies() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let mut vec = new_checked_vec::<u8>();
+        vec.reserve(8);
+        vec.extend_from_slice(&[1, 2, 3]);
+
+        unsafe {
+            let capacity = vec.capacity();
+            vec.as_mut_ptr().add(capacity).write(0);
+        }
+
+        assert!(catch_unwind(AssertUnwindSafe(|| {
+            vec.with_spare_capacity(|spare| {
+                spare.push(4);
+            });
+        }))
+        .is_err());
+        assert!(catch_unwind(AssertUnwindSafe(|| drop(vec))).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn unchecked_preconditions() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let mut vec = new_checked_vec::<u8>();
+        vec.push(1);
+        let capacity = vec.capacity();
+
+        assert!(catch_unwind(AssertUnwindSafe(|| unsafe { vec.set_len_unchecked(capacity + 1) })).is_err());
+        assert!(catch_unwind(AssertUnwindSafe(|| unsafe { vec.remove_unchecked(1) })).is_err());
+        assert!(catch_unwind(AssertUnwindSafe(|| unsafe { vec.swap_remove_unchecked(1) })).is_err());
+        assert!(catch_unwind(AssertUnwindSafe(|| unsafe {
+            vec.insert_unchecked(2, 0);
+        }))
+        .is_err());
+        assert!(catch_unwind(AssertUnwindSafe(|| unsafe { vec.pop_array_unchecked::<2>() })).is_err());
+        assert_eq!(vec, [1]);
+
+        vec.extend(core::iter::repeat(0).take(capacity - 1));
+        assert!(catch_unwind(AssertUnwindSafe(|| unsafe {
+            vec.push_unchecked(0);
+        }))
+        .is_err());
+        assert_eq!(vec.len(), capacity);
+
+        vec.clear();
+        assert!(catch_unwind(AssertUnwindSafe(|| unsafe { vec.pop_unchecked() })).is_err());
+    }
+}
+
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+mod anon_mmap {
+    use generic_vec::{raw::AnonMmap, GenericVec};
+
+    macro_rules! new_vec {
+        ($vec:pat, max($len:expr)) => {
+            let $vec = generic_vec::GenericVec::with_storage(generic_vec::raw::AnonMmap::new());
+        };
+    }
+
+    make_tests_files!();
+
+    #[test]
+    fn grow_and_shrink() {
+        let mut vec = GenericVec::with_storage(AnonMmap::<u64>::new().with_huge_pages());
+        assert!(vec.storage().huge_pages());
+
+        vec.extend(0..1_000_000);
+        assert!((0..1_000_000).eq(vec.iter().copied()));
+
+        vec.truncate(1000);
+        vec.release_spare();
+        assert!(vec.capacity() >= 1_000_000);
+        assert!((0..1000).eq(vec.iter().copied()));
+
+        vec.shrink_to(2000);
+        assert!(vec.capacity() >= 2000 && vec.capacity() < 1_000_000);
+        assert!((0..1000).eq(vec.iter().copied()));
+
+        vec.clear();
+        vec.shrink_to_fit();
+        assert_eq!(vec.capacity(), 0);
+
+        vec.push(10);
+        assert_eq!(vec, [10]);
+    }
+
+    #[test]
+    fn released_pages_are_reusable() {
+        let mut vec = GenericVec::<u8, AnonMmap<u8>>::with_capacity(1 << 16);
+        let capacity = vec.capacity();
+        assert!(capacity >= 1 << 16);
+
+        vec.extend(core::iter::repeat(1).take(capacity));
+        vec.truncate(10);
+        vec.release_spare();
+
+        vec.extend(core::iter::repeat(2).take(capacity - 10));
+        assert_eq!(vec.capacity(), capacity);
+        assert!(vec[..10].iter().all(|&x| x == 1));
+        assert!(vec[10..].iter().all(|&x| x == 2));
+    }
+}
+
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+mod reserved {
+    use generic_vec::{raw::Reserved, GenericVec};
+
+    macro_rules! new_vec {
+        ($vec:pat, max($len:expr)) => {
+            let $vec = generic_vec::GenericVec::with_storage(generic_vec::raw::Reserved::new(1 << 20).unwrap());
+        };
+    }
+
+    make_tests_files!();
+
+    #[test]
+    fn address_stable() {
+        let mut vec = GenericVec::with_storage(Reserved::<u64>::new(1 << 24).unwrap());
+        vec.push(0);
+        let ptr = vec.as_ptr();
+
+        for i in 1..1_000_000 {
+            vec.push(i);
+        }
+
+        assert_eq!(vec.as_ptr(), ptr);
+        assert!((0..1_000_000).eq(vec.iter().copied()));
+    }
+
+    #[test]
+    fn exhausted() {
+        let mut vec = GenericVec::with_storage(Reserved::<u8>::new(100).unwrap());
+        let max = vec.storage().max_capacity();
+        assert!(max >= 100);
+
+        assert!(vec.try_reserve(max));
+        assert!(!vec.try_reserve(max + 1));
+
+        vec.extend(core::iter::repeat(1).take(max));
+        assert!(vec.try_push(0).is_err());
+    }
+
+    #[test]
+    fn appender() {
+        let mut vec = GenericVec::with_storage(Reserved::new(1 << 20).unwrap());
+        vec.push(S!(0));
+
+        let appender = vec.appender();
+        let refs: Vec<&String> = (1..10_000).map(|i| appender.push(S!(i))).collect();
+        assert_eq!(appender.len(), 10_000);
+
+        for (i, value) in refs.iter().enumerate() {
+            assert_eq!(**value, S!(i as i32 + 1));
+        }
+
+        drop(appender);
+        assert_eq!(vec.len(), 10_000);
+        assert_eq!(vec[9_999], S!(9_999));
+    }
+}
+
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+mod mmap {
+    use generic_vec::MmapVec;
+    use std::path::PathBuf;
+
+    struct TempFile(PathBuf);
+
+    impl TempFile {
+        fn new(name: &str) -> Self {
+            Self(std::env::temp_dir().join(format!("generic-vec-{}-{}.bin", name, std::process::id())))
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) { let _ = std::fs::remove_file(&self.0); }
+    }
+
+    #[test]
+    fn reopen() {
+        let file = TempFile::new("reopen");
+
+        let mut vec = unsafe { MmapVec::<[u64; 2]>::create(&file.0).unwrap() };
+        for i in 0..1000 {
+            vec.push([i, !i]);
+        }
+        vec.flush().unwrap();
+        vec.truncate(10);
+        drop(vec);
+
+        let mut vec = unsafe { MmapVec::<[u64; 2]>::open(&file.0).unwrap() };
+        assert_eq!(vec.len(), 10);
+        assert_eq!(vec[9], [9, !9]);
+
+        for i in 10..500 {
+            vec.push([i, !i]);
+        }
+        vec.sync_range(490..).unwrap();
+        drop(vec);
+
+        let vec = unsafe { MmapVec::<[u64; 2]>::open(&file.0).unwrap() };
+        assert_eq!(vec.len(), 500);
+        assert_eq!(vec[499], [499, !499]);
+    }
+
+    #[test]
+    fn reopen_after_into_iter() {
+        let file = TempFile::new("reopen_after_into_iter");
+
+        let mut vec = unsafe { MmapVec::<u32>::create(&file.0).unwrap() };
+        vec.extend(0..10);
+        vec.flush().unwrap();
+        let mut iter = vec.into_iter();
+        assert_eq!(iter.next(), Some(0));
+        drop(iter);
+
+        let mut vec = unsafe { MmapVec::<u32>::open(&file.0).unwrap() };
+        assert!(vec.is_empty());
+
+        vec.extend(0..10);
+        vec.flush().unwrap();
+        drop(vec.into_iter().into_storage());
+
+        let vec = unsafe { MmapVec::<u32>::open(&file.0).unwrap() };
+        assert!(vec.is_empty());
+    }
+
+    #[test]
+    fn reserve() {
+        let file = TempFile::new("reserve");
+
+        let mut vec = unsafe { MmapVec::<u32>::create(&file.0).unwrap() };
+        assert_eq!(vec.capacity(), 0);
+        vec.reserve(100);
+        assert!(vec.capacity() >= 100);
+        assert!(vec.try_reserve(1000));
+        assert!(vec.capacity() >= 1000);
+        assert!(!vec.try_reserve(usize::MAX));
+        assert!(vec.capacity() >= 1000);
+    }
+
+    #[test]
+    fn open_invalid() {
+        let file = TempFile::new("open-invalid");
+
+        drop(unsafe { MmapVec::<u32>::create(&file.0).unwrap() });
+        assert!(unsafe { MmapVec::<u64>::open(&file.0) }.is_err());
+
+        std::fs::write(&file.0, [0; 100]).unwrap();
+        assert!(unsafe { MmapVec::<u32>::open(&file.0) }.is_err());
+    }
+}
+
+mod counting {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use generic_vec::{
+        raw::{Counting, CountingEvent, UninitBuffer},
+        GenericVec,
+    };
+
+    macro_rules! new_vec {
+        ($vec:pat, max($len:expr)) => {
+            let $vec = crate::counting::new_array_vec::<_, $len>();
+        };
+    }
+
+    pub fn new_array_vec<T, const N: usize>() -> GenericVec<T, Counting<UninitBuffer<[T; N]>>> {
+        GenericVec::with_storage(Counting::new(UninitBuffer::uninit()))
+    }
+
+    make_tests_files!();
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn stats() {
+        use generic_vec::raw::Heap;
+
+        let mut vec = GenericVec::with_storage(Counting::new(Heap::<u32>::new()));
+        for i in 0..100 {
+            vec.push(i);
+        }
+
+        let stats = vec.storage().stats();
+        assert!(stats.reserve_calls >= stats.reallocations);
+        assert_eq!(stats.reallocations, 6);
+        assert_eq!(stats.peak_capacity, vec.capacity());
+        assert_eq!(stats.failed_try_reserves, 0);
+
+        vec.clear();
+        vec.reserve(10);
+        assert_eq!(vec.storage().stats().reallocations, 6);
+
+        let vec = GenericVec::<u32, Counting<Heap<u32>>>::with_capacity(10);
+        let stats = vec.storage().stats();
+        assert_eq!(stats.reallocations, 0);
+        assert_eq!(stats.peak_capacity, vec.capacity());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn unused_capacity() {
+        use generic_vec::raw::Heap;
+
+        let mut vec = GenericVec::with_storage(Counting::new(Heap::<u32>::new()));
+        let mut expected = 0;
+
+        for i in 0..100 {
+            let capacity = vec.capacity();
+            vec.push(i);
+
+            if vec.capacity() != capacity {
+                expected = expected.max(vec.capacity() - vec.len());
+            }
+        }
+
+        let stats = vec.storage().stats();
+        assert!(stats.peak_unused_capacity > 0);
+        assert_eq!(stats.peak_unused_capacity, expected);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn forwards_drop() {
+        use generic_vec::raw::VecStorage;
+
+        let mut vec = std::vec![1, 2, 3];
+
+        {
+            let (len, storage) = VecStorage::new(&mut vec);
+            let mut gvec = unsafe { GenericVec::from_raw_parts(len, Counting::new(storage)) };
+            gvec.push(4);
+        }
+
+        assert_eq!(vec, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn hook() {
+        static GROW: AtomicUsize = AtomicUsize::new(0);
+        static FAILED: AtomicUsize = AtomicUsize::new(0);
+
+        fn hook(event: CountingEvent) {
+            match event {
+                CountingEvent::Grow { .. } => GROW.fetch_add(1, Ordering::Relaxed),
+                CountingEvent::TryReserveFailed { capacity, new_capacity } => {
+                    assert_eq!((capacity, new_capacity), (4, 5));
+                    FAILED.fetch_add(1, Ordering::Relaxed)
+                }
+            };
+        }
+
+        let mut vec =
+            GenericVec::<u8, _>::with_storage(Counting::new(UninitBuffer::<[u8; 4]>::uninit()).with_hook(hook));
+        vec.extend_from_slice(&[1, 2, 3, 4]);
+        assert!(!vec.try_reserve(1));
+        assert!(!vec.try_reserve(1));
+
+        let stats = vec.storage().stats();
+        assert_eq!(stats.reallocations, 0);
+        assert_eq!(stats.failed_try_reserves, 2);
+        assert_eq!(GROW.load(Ordering::Relaxed), 0);
+        assert_eq!(FAILED.load(Ordering::Relaxed), 2);
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod limited {
+    use generic_vec::{
+        raw::{Budget, Counting, Heap, Limited, SharedLimited},
+        GenericVec,
+    };
+
+    macro_rules! new_vec {
+        ($vec:pat, max($len:expr)) => {
+            let $vec = crate::limited::new_limited_vec($len);
+        };
+    }
+
+    pub fn new_limited_vec<T>(max_capacity: usize) -> GenericVec<T, Limited<Heap<T>>> {
+        GenericVec::with_storage(Limited::new(Heap::new(), max_capacity))
+    }
+
+    make_tests_files!();
+
+    #[test]
+    fn element_limit() {
+        let mut vec = GenericVec::with_storage(Limited::new(Heap::<u64>::new(), 5));
+        assert_eq!(vec.storage().max_capacity::<u64>(), 5);
+
+        vec.extend(0..5);
+        assert_eq!(vec.capacity(), 5);
+        assert!(!vec.try_reserve(1));
+        assert_eq!(vec.try_push(5), Err(5));
+
+        vec.clear();
+        assert!(vec.try_reserve(5));
+    }
+
+    #[test]
+    fn byte_limit() {
+        let mut vec = GenericVec::with_storage(Limited::with_byte_limit(Heap::<u32>::new(), 30));
+        assert_eq!(vec.storage().max_capacity::<u32>(), 7);
+
+        vec.extend(0..7);
+        assert_eq!(vec, [0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(vec.try_push(7), Err(7));
+    }
+
+    #[test]
+    fn fixed_capacity() {
+        use generic_vec::raw::{FixedCapacity, Storage, UninitBuffer};
+
+        type Fixed = Limited<UninitBuffer<[u32; 4]>>;
+        assert_eq!(<Fixed as FixedCapacity<u32>>::CAPACITY, 4);
+        assert_eq!(<Fixed as Storage<u32>>::CONST_CAPACITY, Some(4));
+
+        let vec = GenericVec::from_array_in([1, 2, 3, 4], Limited::new(UninitBuffer::<[u32; 4]>::uninit(), 2));
+        assert_eq!(vec.capacity(), 4);
+        assert_eq!(vec, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn forwards_drop() {
+        use generic_vec::raw::VecStorage;
+
+        let mut vec = std::vec![1, 2, 3];
+
+        {
+            let (len, storage) = VecStorage::new(&mut vec);
+            let mut gvec = unsafe { GenericVec::from_raw_parts(len, Limited::new(storage, 8)) };
+            gvec.push(4);
+        }
+
+        assert_eq!(vec, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn inner_storage_stays_within_limit() {
+        let mut vec = GenericVec::with_storage(Limited::new(Counting::new(Heap::<u32>::new()), 10));
+
+        for i in 0..10 {
+            vec.push(i);
+            assert!(vec.storage().get().stats().peak_capacity <= 10);
+        }
+
+        assert_eq!(vec.storage().get().stats().peak_capacity, 10);
+        assert!(!vec.try_reserve(11));
+        assert_eq!(vec.storage().get().stats().peak_capacity, 10);
+    }
+
+    #[test]
+    fn inner_storage_stays_within_budget() {
+        let budget = Budget::new(40);
+        let mut vec = GenericVec::with_storage(SharedLimited::new(Counting::new(Heap::<u32>::new()), &budget));
+
+        for i in 0..10 {
+            vec.push(i);
+            assert!(vec.storage().get().stats().peak_capacity * 4 <= vec.storage().charged());
+        }
+
+        assert_eq!(vec.storage().get().stats().peak_capacity, 10);
+        assert_eq!(budget.remaining(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn reserve_panics() {
+        let mut vec = GenericVec::<u8, _>::with_storage(Limited::new(Heap::<u8>::new(), 4));
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| vec.reserve(5)));
+        assert!(result.is_err());
+        assert!(vec.capacity() < 5);
+    }
+
+    #[test]
+    fn shared_budget() {
+        let budget = Budget::new(64);
+
+        let mut a = GenericVec::<u32, _>::with_storage(SharedLimited::new(Heap::<u32>::new(), &budget));
+        let mut b = GenericVec::<u32, _>::with_storage(SharedLimited::new(Heap::<u32>::new(), &budget));
+
+        a.extend(0..8);
+        assert!(a.storage().charged() >= 32);
+        assert_eq!(budget.remaining(), 64 - a.storage().charged());
+
+        assert!(b.try_reserve(16 - a.capacity()));
+        assert!(!b.try_reserve(16 - a.capacity() + 1));
+        assert_eq!(budget.remaining(), 64 - a.storage().charged() - b.storage().charged());
+
+        drop(a);
+        drop(b);
+        assert_eq!(budget.remaining(), 64);
+    }
+
+    #[test]
+    fn shared_budget_zero_sized() {
+        let budget = Budget::new(0);
+        let mut vec = GenericVec::with_storage(SharedLimited::new(Heap::<()>::new(), &budget));
+        vec.extend(core::iter::repeat(()).take(100));
+        assert_eq!(vec.len(), 100);
+        assert_eq!(vec.storage().charged(), 0);
+    }
+}
+
+mod dyn_storage {
+    use generic_vec::{ArrayVec, DynVec, GenericVec, SliceVec};
+
+    fn fill(vec: &mut DynVec<'_, i32>, count: i32) {
+        for i in 0..count {
+            if vec.try_push(i).is_err() {
+                break
+            }
+        }
+    }
+
+    #[test]
+    fn coerce() {
+        let mut array_vec = ArrayVec::<i32, 4>::new();
+        fill(&mut array_vec, 10);
+        assert_eq!(array_vec, [0, 1, 2, 3]);
+
+        let mut buf = generic_vec::uninit_array!(2);
+        let mut slice_vec = SliceVec::new(&mut buf);
+        fill(&mut slice_vec, 10);
+        assert_eq!(slice_vec, [0, 1]);
+    }
+
+    #[test]
+    fn methods() {
+        let mut array_vec = ArrayVec::<i32, 8>::new();
+        array_vec.extend_from_slice(&[1, 2, 3, 4, 5]);
+
+        let vec = array_vec.as_dyn_mut();
+        assert_eq!(vec.capacity(), 8);
+        assert!(!vec.try_reserve(4));
+        vec.retain(|x| *x % 2 == 1);
+        assert_eq!(vec.remove(1), 3);
+        vec.insert(0, 0);
+        vec.drain(..1);
+        assert_eq!(vec.pop(), 5);
+
+        assert_eq!(array_vec.as_dyn().as_slice(), [1]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn grow() {
+        fn extend(vec: &mut DynVec<'_, i32>, count: i32) {
+            for i in 0..count {
+                vec.push(i);
+            }
+        }
+
+        let mut vec = generic_vec::HeapVec::new();
+        extend(&mut vec, 100);
+        assert!((0..100).eq(vec.iter().copied()));
+
+        let vec: &mut DynVec<'_, i32> = &mut vec;
+        vec.clear();
+        vec.reserve(1000);
+        assert!(vec.capacity() >= 1000);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn dyn_storage_is_not_aligned() {
+        fn check<S: generic_vec::raw::Storage<i32> + ?Sized>(_: &GenericVec<i32, S>) -> bool {
+            <S as generic_vec::raw::Storage<i32>>::IS_ALIGNED
+        }
+
+        let vec = generic_vec::HeapVec::<i32>::new();
+        assert!(check(&vec));
+        assert!(!check(vec.as_dyn()));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn forwards_drop() {
+        use generic_vec::raw::VecStorage;
+        use std::boxed::Box;
+
+        let mut vec = std::vec![1, 2, 3];
+
+        {
+            let (len, storage) = VecStorage::new(&mut vec);
+            let mut gvec: Box<DynVec<'_, i32>> = Box::new(unsafe { GenericVec::from_raw_parts(len, storage) });
+            gvec.push(4);
+        }
+
+        assert_eq!(vec, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn boxed_dyn_rejected() {
+        use generic_vec::raw::{DynStorage, Heap};
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let storage: Box<dyn DynStorage<i32>> = Box::new(Heap::<i32>::new());
+        let result = catch_unwind(AssertUnwindSafe(|| GenericVec::with_storage(storage)));
+        assert!(result.is_err());
+    }
+}
+
+mod slice_vec_guard {
+    use generic_vec::{ArrayVec, SliceVec};
+
+    fn shuffle(vec: &mut SliceVec<'_, i32>) {
+        let last = vec.pop();
+        vec.insert(0, last);
+        while vec.try_push(0).is_ok() {}
+    }
+
+    #[test]
+    fn write_back() {
+        let mut vec = ArrayVec::<i32, 6>::new();
+        vec.extend_from_slice(&[1, 2, 3]);
+
+        shuffle(&mut vec.as_slice_vec());
+        assert_eq!(vec, [3, 1, 2, 0, 0, 0]);
+
+        vec.as_slice_vec().clear();
+        assert!(vec.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn fixed_capacity() {
+        let mut vec = generic_vec::HeapVec::<i32>::with_capacity(4);
+        vec.extend_from_slice(&[1, 2]);
+        let capacity = vec.capacity();
+
+        {
+            let mut view = vec.as_slice_vec();
+            assert_eq!(view.capacity(), capacity);
+            assert!(!view.try_reserve(capacity + 1));
+            view.push(3);
+        }
+
+        assert_eq!(vec, [1, 2, 3]);
+        assert_eq!(vec.capacity(), capacity);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn leak() {
+        use std::rc::Rc;
+
+        let rc = Rc::new(());
+        let mut vec = ArrayVec::<Rc<()>, 4>::new();
+        vec.push(rc.clone());
+        vec.push(rc.clone());
+
+        core::mem::forget(vec.as_slice_vec());
+        assert!(vec.is_empty());
+        drop(vec);
+        assert_eq!(Rc::strong_count(&rc), 3);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn swapped() {
+        use std::rc::Rc;
+
+        let rc = Rc::new(());
+        let mut vec = ArrayVec::<Rc<()>, 4>::new();
+        vec.push(rc.clone());
+
+        let mut buf = generic_vec::uninit_array!(2);
+        let mut other = SliceVec::new(&mut buf);
+        other.push(rc.clone());
+        other.push(rc.clone());
+
+        {
+            let mut view = vec.as_slice_vec();
+            core::mem::swap(&mut *view, &mut other);
+        }
+
+        // the elements of `vec` now belong to `other`, and the elements of `other` were dropped
+        assert_eq!(other.len(), 1);
+        assert_eq!(Rc::strong_count(&rc), 2);
+
+        drop(other);
+        assert_eq!(Rc::strong_count(&rc), 1);
+        assert!(vec.is_empty());
+    }
+}
+
+mod arena {
+    use core::mem::MaybeUninit;
+    use generic_vec::{
+        raw::{Arena, ArenaBuffer},
+        GenericVec,
+    };
+
+    #[test]
+    fn grow_in_place() {
+        let mut buf = [MaybeUninit::uninit(); 64];
+        let buffer = ArenaBuffer::new(&mut buf);
+
+        let mut vec = GenericVec::with_storage(Arena::<u32>::new(&buffer));
+        vec.extend_from_slice(&[1, 2, 3, 4]);
+        let ptr = vec.as_ptr();
+        vec.push(5);
+        assert_eq!(vec.as_ptr(), ptr);
+        assert_eq!(vec, [1, 2, 3, 4, 5]);
+
+        drop(vec);
+        assert_eq!(buffer.remaining(), 64);
+    }
+
+    #[test]
+    fn shared() {
+        let mut buf = [MaybeUninit::uninit(); 256];
+        let buffer = ArenaBuffer::new(&mut buf);
+
+        let mut a = GenericVec::with_storage(Arena::<u16>::new(&buffer));
+        let mut b = GenericVec::with_storage(Arena::<u64>::new(&buffer));
+
+        for i in 0..8 {
+            a.push(i as u16);
+            b.push(i);
+        }
+
+        assert_eq!(a, [0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(b, [0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(b.as_ptr() as usize % core::mem::align_of::<u64>(), 0);
+    }
+
+    #[test]
+    fn exhausted() {
+        let mut buf = [MaybeUninit::uninit(); 16];
+        let mut buffer = ArenaBuffer::new(&mut buf);
+
+        {
+            let mut vec = GenericVec::with_storage(Arena::<u8>::new(&buffer));
+            assert!(vec.try_reserve(16));
+            assert!(!vec.try_reserve(17));
+            vec.extend_from_slice(&[0; 16]);
+            assert_eq!(buffer.remaining(), 0);
+            core::mem::forget(vec);
+        }
+
+        buffer.reset();
+        assert_eq!(buffer.remaining(), 16);
+    }
+
+    #[test]
+    fn reset_after_drop() {
+        let mut buf = [MaybeUninit::uninit(); 64];
+        let mut buffer = ArenaBuffer::new(&mut buf);
+
+        let mut a = GenericVec::with_storage(Arena::<u8>::new(&buffer));
+        let mut b = GenericVec::with_storage(Arena::<u32>::new(&buffer));
+        a.extend_from_slice(b"hello");
+        b.extend_from_slice(&[1, 2, 3]);
+        // `a` can't free its allocation, because `b` was allocated after it
+        drop(a);
+        assert!(buffer.remaining() < 64);
+        drop(b);
+
+        buffer.reset();
+        assert_eq!(buffer.remaining(), 64);
+
+        let mut c = GenericVec::with_storage(Arena::<u64>::new(&buffer));
+        c.extend_from_slice(&[1; 8]);
+        assert_eq!(buffer.remaining(), 0);
+    }
+}
+
+mod pool {
+    use generic_vec::{
+        raw::{Pool, PoolSlot},
+        GenericVec,
+    };
+
+    #[test]
+    fn take_and_return() {
+        let mut slab = [PoolSlot::<u16, 8>::NEW; 2];
+        let pool = Pool::new(&mut slab);
+
+        let mut a = GenericVec::with_storage(pool.block());
+        assert_eq!(a.capacity(), 0);
+        assert_eq!(pool.available(), 2);
+
+        a.extend_from_slice(&[1, 2, 3]);
+        assert_eq!(a.capacity(), 8);
+        assert_eq!(pool.available(), 1);
+
+        let mut b = GenericVec::with_storage(pool.block());
+        b.extend_from_slice(&[4, 5, 6, 7, 8, 9, 10, 11]);
+        assert!(!b.try_reserve(1));
+        assert_eq!(pool.available(), 0);
+
+        let mut c = GenericVec::with_storage(pool.block());
+        assert!(!c.try_reserve(1));
+
+        drop(a);
+        assert_eq!(pool.available(), 1);
+        c.push(12);
+        assert_eq!(c, [12]);
+        assert_eq!(b, [4, 5, 6, 7, 8, 9, 10, 11]);
+
+        drop((b, c));
+        assert_eq!(pool.available(), 2);
+    }
+
+    #[test]
+    fn aligned() {
+        let mut slab = [PoolSlot::<u128, 3>::NEW; 3];
+        let pool = Pool::new(&mut slab);
+
+        let vecs = [(); 3].map(|()| {
+            let mut vec = GenericVec::with_storage(pool.block());
+            vec.push(u128::MAX);
+            vec
+        });
+
+        for vec in &vecs {
+            assert_eq!(vec.as_ptr() as usize % core::mem::align_of::<u128>(), 0);
+            assert_eq!(*vec, [u128::MAX]);
+        }
+    }
+}
+
+#[test]
+fn grow_copy_bytes() {
+    let mut buf = generic_vec::uninit_array!(16);
+    let mut vec = generic_vec::SliceVec::new(&mut buf);
+    vec.push(1_u8);
+    vec.grow_copy(10, 0xab);
+    vec.resize_copy(12, 0xcd);
+    assert_eq!(vec, [
+        1, 0xab, 0xab, 0xab, 0xab, 0xab, 0xab, 0xab, 0xab, 0xab, 0xab, 0xcd
+    ]);
+
+    let mut buf = generic_vec::uninit_array!(4);
+    let mut vec = generic_vec::SliceVec::new(&mut buf);
+    vec.grow_copy(4, -1_i8);
+    assert_eq!(vec, [-1; 4]);
+
+    let mut buf = generic_vec::uninit_array!(8);
+    let mut vec = generic_vec::SliceVec::<u8>::new(&mut buf);
+    vec.grow_bytes(3, 0x11_u8);
+    vec.resize_bytes(6, 0x22);
+    assert_eq!(vec, [0x11, 0x11, 0x11, 0x22, 0x22, 0x22]);
+    vec.resize_bytes(2, 0x33);
+    assert_eq!(vec, [0x11, 0x11]);
+
+    let mut buf = generic_vec::uninit_array!(4);
+    let mut vec = generic_vec::SliceVec::<i8>::new(&mut buf);
+    vec.resize_bytes(4, -2_i8);
+    assert_eq!(vec, [-2; 4]);
+}
+
+#[cfg(feature = "std")]
+mod with_spare_capacity {
+    use generic_vec::{HeapVec, SliceVec};
+
+    #[test]
+    fn commit_on_unwind() {
+        let mut vec: HeapVec<String> = HeapVec::new();
+        vec.reserve(4);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            vec.with_spare_capacity(|spare| {
+                spare.push("a".to_string());
+                spare.push("b".to_string());
+                panic!("oops");
+            })
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(vec[..], ["a", "b"]);
+    }
+
+    #[test]
+    fn swapped_out() {
+        let mut vec: HeapVec<String> = HeapVec::new();
+        vec.reserve(4);
+
+        vec.with_spare_capacity(|spare| {
+            spare.push("a".to_string());
+
+            let mut other = SliceVec::new(Box::leak(Box::new(generic_vec::uninit_array!(4))));
+            other.push("other".to_string());
+            core::mem::swap(spare, &mut other);
+
+            // `other` now refers to `vec`'s spare capacity
+            assert_eq!(other[..], ["a"]);
+        });
+
+        // the elements in the swapped in `SliceVec` are dropped instead of being added
+        assert!(vec.is_empty());
+    }
+}
+
+mod fixed_capacity {
+    use generic_vec::{
+        gvec,
+        raw::{FixedCapacity, UninitBuffer, ZeroSized},
+        ArrayVec, GenericVec, InitArrayVec,
+    };
+
+    #[test]
+    fn capacity() {
+        assert_eq!(<[u8; 3] as FixedCapacity<u8>>::CAPACITY, 3);
+        assert_eq!(<UninitBuffer<[u16; 4]> as FixedCapacity<u8>>::CAPACITY, 8);
+        assert_eq!(<UninitBuffer<[u8; 1], u32> as FixedCapacity<u16>>::CAPACITY, 2);
+        assert_eq!(<ZeroSized<()> as FixedCapacity<()>>::CAPACITY, usize::MAX);
+
+        let vec = InitArrayVec::<u8, 3>::new([0; 3]);
+        assert_eq!(vec.capacity(), <[u8; 3] as FixedCapacity<u8>>::CAPACITY);
+    }
+
+    #[test]
+    fn from_array_in() {
+        let vec = GenericVec::from_array_in([1_u8, 2, 3], UninitBuffer::<[u16; 2]>::uninit());
+        assert_eq!(vec, [1, 2, 3]);
+        assert_eq!(vec.capacity(), 4);
+
+        let vec = GenericVec::from_array_in([(); 3], ZeroSized::<()>::NEW);
+        assert_eq!(vec.len(), 3);
+    }
+
+    #[test]
+    fn from_array() {
+        let vec = ArrayVec::<i32, 4>::from([1, 2]);
+        assert_eq!(vec, [1, 2]);
+        assert_eq!(vec.capacity(), 4);
+
+        let vec = ArrayVec::<i32, 2>::from([1, 2]);
+        assert!(vec.is_full());
+        assert_eq!(vec.into_array(), [1, 2]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn from_array_drops() {
+        use std::rc::Rc;
+
+        let rc = Rc::new(());
+        let vec = ArrayVec::<Rc<()>, 3>::from([rc.clone(), rc.clone()]);
+        assert_eq!(Rc::strong_count(&rc), 3);
+        drop(vec);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    #[test]
+    fn gvec() {
+        let vec: ArrayVec<i32, 4> = gvec![1, 2, 3];
+        assert_eq!(vec, [1, 2, 3]);
+
+        let vec: ArrayVec<i32, 3> = gvec![1, 2, 3];
+        assert!(vec.is_full());
+    }
+}
+
+mod collect {
+    use generic_vec::{
+        iter::IteratorExt,
+        raw::{UninitBuffer, UninitSlice},
+        uninit_array, ArrayVec,
+    };
+
+    #[test]
+    fn collect_in() {
+        let mut buf = uninit_array!(4);
+        let vec = (0..3).collect_in(UninitSlice::<u32>::from_mut(&mut buf));
+        assert_eq!(vec, [0, 1, 2]);
+        assert_eq!(vec.capacity(), 4);
+
+        // iterators with an unknown length
+        let vec = (0..10)
+            .filter(|x| x % 3 == 0)
+            .collect_in(UninitBuffer::<[i32; 4]>::uninit());
+        assert_eq!(vec, [0, 3, 6, 9]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn collect_in_grows() {
+        let vec = (0..100)
+            .filter(|x| x % 2 == 0)
+            .collect_in(generic_vec::raw::Heap::<i32>::new());
+        assert!(vec.iter().copied().eq((0..100).step_by(2)));
+    }
+
+    #[test]
+    fn try_collect_in() {
+        let vec = (0..3).try_collect_in(UninitBuffer::<[i32; 3]>::uninit()).unwrap();
+        assert_eq!(vec, [0, 1, 2]);
+
+        let (vec, overflow) = (0..10)
+            .filter(|x| x % 2 == 1)
+            .try_collect_in(UninitBuffer::<[i32; 3]>::uninit())
+            .unwrap_err();
+        assert_eq!(vec, [1, 3, 5]);
+        assert_eq!(overflow.item, 7);
+        assert!(overflow.into_iter().eq([7, 9].iter().copied()));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn try_collect_in_drops() {
+        use std::rc::Rc;
+
+        let rc = Rc::new(());
+        let items = core::iter::repeat(rc.clone()).take(5);
+        let (vec, overflow) = items.try_collect_in(UninitBuffer::<[Rc<()>; 2]>::uninit()).unwrap_err();
+        assert_eq!(vec.len(), 2);
+        assert_eq!(Rc::strong_count(&rc), 5);
+
+        drop(overflow);
+        assert_eq!(Rc::strong_count(&rc), 3);
+        drop(vec);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    #[test]
+    fn collect_into() {
+        let mut vec = ArrayVec::<i32, 6>::new();
+        vec.push(-1);
+
+        (0..3).collect_into(&mut vec).push(3);
+        assert_eq!(vec, [-1, 0, 1, 2, 3]);
+
+        (4..5).collect_into(vec.as_dyn_mut());
+        assert_eq!(vec, [-1, 0, 1, 2, 3, 4]);
+    }
+}
+
+mod constructors {
+    use generic_vec::{
+        raw::{UninitBuffer, ZeroSized},
+        ArrayVec, GenericVec,
+    };
+
+    #[test]
+    fn from_fn() {
+        let vec = ArrayVec::<usize, 4>::from_fn(4, |i| i * 2);
+        assert_eq!(vec, [0, 2, 4, 6]);
+
+        assert!(ArrayVec::<usize, 4>::try_from_fn(5, |i| i).is_none());
+        assert_eq!(ArrayVec::<usize, 4>::try_from_fn(0, |i| i).unwrap(), []);
+
+        let vec = GenericVec::from_fn_in(ZeroSized::<()>::NEW, 10, |_| ());
+        assert_eq!(vec.len(), 10);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn from_fn_heap() {
+        use std::boxed::Box;
+
+        let vec = generic_vec::HeapVec::<Box<usize>>::from_fn(3, Box::new);
+        assert!(vec.iter().map(|x| **x).eq(0..3));
+
+        let vec = generic_vec::HeapVec::<Box<usize>>::try_from_fn(3, Box::new).unwrap();
+        assert!(vec.iter().map(|x| **x).eq(0..3));
+    }
+
+    #[test]
+    fn try_in() {
+        let mut calls = 0;
+        let result = GenericVec::try_from_fn_in(UninitBuffer::<[u8; 2]>::uninit(), 3, |i| {
+            calls += 1;
+            i as u8
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 0);
+
+        let vec = GenericVec::try_from_slice_in(UninitBuffer::<[u8; 3]>::uninit(), b"abc")
+            .ok()
+            .unwrap();
+        assert_eq!(vec, *b"abc");
+        assert!(GenericVec::try_from_slice_in(UninitBuffer::<[u8; 3]>::uninit(), b"abcd").is_err());
+
+        let vec = GenericVec::try_from_elem_in(UninitBuffer::<[u16; 2]>::uninit(), 7_u8, 4)
+            .ok()
+            .unwrap();
+        assert_eq!(vec, [7; 4]);
+        assert!(GenericVec::try_from_elem_in(UninitBuffer::<[u16; 2]>::uninit(), 7_u8, 5).is_err());
+
+        let vec = GenericVec::try_from_copy_slice_in(UninitBuffer::<[u8; 3]>::uninit(), b"abc")
+            .ok()
+            .unwrap();
+        assert_eq!(vec, *b"abc");
+        assert!(GenericVec::try_from_copy_slice_in(UninitBuffer::<[u8; 3]>::uninit(), b"abcd").is_err());
+
+        let vec = GenericVec::try_from_copy_elem_in(UninitBuffer::<[u16; 2]>::uninit(), 7_u8, 4)
+            .ok()
+            .unwrap();
+        assert_eq!(vec, [7; 4]);
+        assert!(GenericVec::try_from_copy_elem_in(UninitBuffer::<[u16; 2]>::uninit(), 7_u8, 5).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn from_slice_and_elem() {
+        use std::boxed::Box;
+
+        let vec = GenericVec::from_slice_in(generic_vec::raw::Heap::<Box<i32>>::new(), &[Box::new(1)]);
+        assert_eq!(vec[..], [Box::new(1)]);
+
+        let vec = GenericVec::from_elem_in(UninitBuffer::<[Box<i32>; 4]>::uninit(), Box::new(2), 3);
+        assert_eq!(vec[..], [Box::new(2), Box::new(2), Box::new(2)]);
+
+        let vec = GenericVec::from_elem_in(UninitBuffer::<[Box<i32>; 4]>::uninit(), Box::new(2), 0);
+        assert!(vec.is_empty());
+    }
+
+    #[test]
+    fn repeat() {
+        let mut vec = ArrayVec::<i32, 8>::from([1, 2, 3]);
+        vec.repeat(1);
+        assert_eq!(vec, [1, 2, 3]);
+        vec.repeat(2);
+        assert_eq!(vec, [1, 2, 3, 1, 2, 3]);
+        vec.repeat(0);
+        assert!(vec.is_empty());
+        vec.repeat(100);
+        assert!(vec.is_empty());
+        vec.repeat(usize::MAX);
+        assert!(vec.is_empty());
+
+        let mut vec = ArrayVec::<u8, 7>::from([9]);
+        vec.repeat(7);
+        assert_eq!(vec, [9; 7]);
+    }
+
+    #[test]
+    fn try_repeat() {
+        let mut vec = ArrayVec::<i32, 5>::from([1, 2]);
+        assert!(!vec.try_repeat(3));
+        assert_eq!(vec, [1, 2]);
+        assert!(!vec.try_repeat(usize::MAX));
+        assert!(vec.try_repeat(2));
+        assert_eq!(vec, [1, 2, 1, 2]);
+        assert!(vec.try_repeat(0));
+        assert!(vec.is_empty());
+        assert!(vec.try_repeat(usize::MAX));
+        assert!(vec.is_empty());
+    }
+
+    #[test]
+    fn repeat_copy() {
+        let mut vec = ArrayVec::<i32, 16>::from([1, 2, 3]);
+        vec.repeat_copy(5);
+        assert_eq!(vec, [1, 2, 3, 1, 2, 3, 1, 2, 3, 1, 2, 3, 1, 2, 3]);
+        vec.repeat_copy(0);
+        assert!(vec.is_empty());
+        vec.repeat_copy(usize::MAX);
+        assert!(vec.is_empty());
+
+        let mut vec = ArrayVec::<i32, 5>::from([1, 2]);
+        assert!(!vec.try_repeat_copy(3));
+        assert!(!vec.try_repeat_copy(usize::MAX));
+        assert_eq!(vec, [1, 2]);
+        assert!(vec.try_repeat_copy(2));
+        assert_eq!(vec, [1, 2, 1, 2]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn repeat_clone() {
+        use std::rc::Rc;
+
+        let rc = Rc::new(());
+        let mut vec = generic_vec::HeapVec::<Rc<()>>::from_elem_in(generic_vec::raw::Heap::new(), rc.clone(), 2);
+        vec.repeat(3);
+        assert_eq!(vec.len(), 6);
+        assert_eq!(Rc::strong_count(&rc), 7);
+        drop(vec);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    #[cfg(feature = "std")]
+    mod unwind {
+        use generic_vec::{ArrayVec, GenericVec};
+        use std::{cell::Cell, panic, rc::Rc};
+
+        struct Bomb<'a> {
+            clones: &'a Cell<usize>,
+            rc: Rc<()>,
+        }
+
+        impl Clone for Bomb<'_> {
+            fn clone(&self) -> Self {
+                if self.clones.get() == 0 {
+                    panic!("boom")
+                }
+                self.clones.set(self.clones.get() - 1);
+
+                Bomb {
+                    clones: self.clones,
+                    rc: self.rc.clone(),
+                }
+            }
+        }
+
+        #[test]
+        fn from_fn() {
+            let rc = Rc::new(());
+
+            let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+                ArrayVec::<Rc<()>, 4>::from_fn(4, |i| {
+                    if i == 2 {
+                        panic!("boom")
+                    }
+                    rc.clone()
+                })
+            }));
+
+            assert!(result.is_err());
+            assert_eq!(Rc::strong_count(&rc), 1);
+        }
+
+        #[test]
+        fn from_elem_in() {
+            let rc = Rc::new(());
+            let clones = Cell::new(2);
+            let bomb = Bomb {
+                clones: &clones,
+                rc: rc.clone(),
+            };
+
+            let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+                GenericVec::from_elem_in(generic_vec::raw::UninitBuffer::<[Bomb; 8]>::uninit(), bomb, 8)
+            }));
+
+            assert!(result.is_err());
+            assert_eq!(Rc::strong_count(&rc), 1);
+        }
+
+        #[test]
+        fn repeat() {
+            let rc = Rc::new(());
+            let clones = Cell::new(3);
+            let mut vec = ArrayVec::<Bomb, 8>::new();
+            vec.push(Bomb {
+                clones: &clones,
+                rc: rc.clone(),
+            });
+            vec.push(Bomb {
+                clones: &clones,
+                rc: rc.clone(),
+            });
+
+            let result = panic::catch_unwind(panic::AssertUnwindSafe(|| vec.repeat(4)));
+
+            assert!(result.is_err());
+            assert_eq!(vec.len(), 2);
+            assert_eq!(Rc::strong_count(&rc), 3);
+        }
+    }
+}